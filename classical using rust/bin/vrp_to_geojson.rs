@@ -1,3 +1,8 @@
+//! Standalone solution-to-GeoJSON converter, keyed off OSM node IDs.
+//! `optiqroute export` covers solutions solved against a `VrpInstance`
+//! directly; this binary stays for solutions whose location IDs need
+//! resolving back to OSM coordinates via `CoordinateLookup`.
+
 use clap::{Arg, Command};
 use geojson::{GeoJson, Geometry, Value, Feature, FeatureCollection};
 use serde::Deserialize;
@@ -1,27 +1,102 @@
 //! CLI interface for VRP solver
-//! 
+//!
 //! This binary provides a command-line interface for the VRP solver
 //! that can be called from Python or other external programs.
+//!
+//! For a plain solve of a JSON instance, `optiqroute solve` covers the same
+//! ground; this binary remains the entry point for `--check`, `--serve`,
+//! `--format tsplib`, and warm-start/matrix-file settings, none of which
+//! have been ported over yet.
 
 use std::fs;
 use std::env;
 use serde::{Deserialize, Serialize};
 use vrp_solver::*;
+use geojson::{Feature, FeatureCollection, GeoJson, Geometry, Value};
+use serde_json::Map;
 
 #[derive(Deserialize)]
 struct CliInput {
     instance: VrpInstance,
     algorithm: String,
     settings: Option<CliSettings>,
+    /// A previously produced solution to re-optimize from, e.g. yesterday's
+    /// plan with one new order added. Used as-is when still feasible; falls
+    /// back to a cold solve (with a warning in `CliOutput.error`) otherwise.
+    initial_solution: Option<Vec<RouteOutput>>,
+    /// Set internally (never via JSON) when `instance.distance_matrix` was
+    /// already computed by an alternate input format (e.g. `--format
+    /// tsplib`), so `solve_cli_input` shouldn't discard and recompute it.
+    #[serde(skip)]
+    precomputed_distance: bool,
 }
 
 #[derive(Deserialize)]
 struct CliSettings {
     distance_method: Option<String>,
     parallel: Option<bool>,
+    /// Path to a precomputed NxN distance/duration matrix (JSON with
+    /// `distance_matrix`/`duration_matrix`, or a plain CSV distance matrix),
+    /// used in place of geometric distance calculation when set.
+    matrix_file: Option<String>,
+    /// Termination controls for `multi_start`: cap the number of solver
+    /// restarts, the wall-clock budget, and/or stop once the best objective's
+    /// coefficient of variation over a sliding window `(window_size, min_cv)`
+    /// drops below threshold.
+    max_generations: Option<usize>,
+    max_time_ms: Option<u64>,
+    min_cv: Option<(usize, f64)>,
 }
 
-#[derive(Serialize)]
+impl CliSettings {
+    fn has_termination_controls(&self) -> bool {
+        self.max_generations.is_some() || self.max_time_ms.is_some() || self.min_cv.is_some()
+    }
+
+    fn to_termination_config(&self) -> TerminationConfig {
+        let defaults = TerminationConfig::default();
+        TerminationConfig {
+            max_iterations: self.max_generations.or(defaults.max_iterations),
+            max_time_secs: self.max_time_ms.map(|ms| ms as f64 / 1000.0).or(defaults.max_time_secs),
+            min_cv: self.min_cv.map(|(_, threshold)| threshold).or(defaults.min_cv),
+            window_size: self.min_cv.map(|(window, _)| window).unwrap_or(defaults.window_size),
+        }
+    }
+}
+
+/// On-disk shape for `CliSettings.matrix_file` when given as JSON.
+#[derive(Deserialize)]
+struct MatrixFile {
+    distance_matrix: Vec<Vec<f64>>,
+    duration_matrix: Option<Vec<Vec<f64>>>,
+}
+
+/// Load a precomputed distance/duration matrix from `path`. JSON files are
+/// parsed as [`MatrixFile`]; anything else is treated as a CSV distance
+/// matrix (one row per line, comma-separated), with no duration matrix.
+fn load_matrix_file(path: &str) -> Result<(Vec<Vec<f64>>, Option<Vec<Vec<f64>>>), String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read matrix file: {}", e))?;
+
+    if path.ends_with(".csv") {
+        let distance_matrix = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                line.split(',')
+                    .map(|cell| cell.trim().parse::<f64>().map_err(|e| format!("Invalid matrix cell '{}': {}", cell, e)))
+                    .collect::<Result<Vec<f64>, String>>()
+            })
+            .collect::<Result<Vec<Vec<f64>>, String>>()?;
+        Ok((distance_matrix, None))
+    } else {
+        let parsed: MatrixFile = serde_json::from_str(&data)
+            .map_err(|e| format!("Failed to parse matrix JSON: {}", e))?;
+        Ok((parsed.distance_matrix, parsed.duration_matrix))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
 struct CliOutput {
     routes: Vec<RouteOutput>,
     total_distance: f64,
@@ -31,86 +106,247 @@ struct CliOutput {
     solve_time_ms: f64,
     success: bool,
     error: Option<String>,
+    /// Present only when termination controls were supplied in `settings`.
+    termination_reason: Option<TerminationReason>,
+    iterations: Option<usize>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 struct RouteOutput {
     vehicle_id: usize,
     locations: Vec<usize>,
+    stops: Vec<StopOutput>,
     total_distance: f64,
     total_duration: f64,
     total_demand: f64,
 }
 
+/// A visited location's coordinates, carried alongside `RouteOutput` so the
+/// GeoJSON output stage doesn't need to look the instance back up.
+#[derive(Serialize, Deserialize)]
+struct StopOutput {
+    id: usize,
+    lat: f64,
+    lon: f64,
+}
+
+/// A single feasibility problem found while validating a solution against
+/// its instance in `--check` mode.
+#[derive(Serialize, Debug)]
+struct Violation {
+    kind: String,
+    vehicle_id: Option<usize>,
+    location_id: Option<usize>,
+    expected: Option<f64>,
+    actual: Option<f64>,
+    message: String,
+}
+
+/// Tolerance (absolute) when comparing a solution's reported totals against
+/// a fresh recomputation from the distance matrix.
+const CHECK_TOLERANCE: f64 = 1e-3;
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = env::args().collect();
-    
+
+    if args.len() >= 2 && args[1] == "--check" {
+        return run_check(&args);
+    }
+
+    if args.len() >= 2 && args[1] == "--serve" {
+        return run_serve();
+    }
+
     if args.len() < 5 || args[1] != "--input" || args[3] != "--output" {
-        eprintln!("Usage: {} --input <input.json> --output <output.json>", args[0]);
+        eprintln!("Usage: {} --input <input.json> --output <output.json> [--geo-json <output.geojson>]", args[0]);
+        eprintln!("       {} --input <instance.vrp> --output <output.json> --format tsplib [--algorithm <name>]", args[0]);
+        eprintln!("       {} --check --input <input.json> --solution <output.json>", args[0]);
+        eprintln!("       {} --serve", args[0]);
         std::process::exit(1);
     }
-    
+
     let input_file = &args[2];
     let output_file = &args[4];
-    
-    // Read input file
-    let input_data = fs::read_to_string(input_file)
-        .map_err(|e| format!("Failed to read input file: {}", e))?;
-    
-    let cli_input: CliInput = serde_json::from_str(&input_data)
-        .map_err(|e| format!("Failed to parse input JSON: {}", e))?;
-    
-    // Create VRP instance and calculate distance matrix
-    let mut instance = cli_input.instance;
-    
-    // Reinitialize distance matrix with correct size
-    let n = instance.locations.len();
-    instance.distance_matrix = vec![vec![0.0; n]; n];
-    
-    // Determine distance calculation method
-    let distance_method = cli_input.settings
-        .as_ref()
-        .and_then(|s| s.distance_method.as_ref())
+
+    // Optional trailing `--geo-json <file>` flag for a map-ready export
+    let geo_json_file = args.iter()
+        .position(|a| a == "--geo-json")
+        .and_then(|i| args.get(i + 1))
+        .cloned();
+
+    // `--format tsplib` ingests a standard TSPLIB/CVRPLIB instance directly
+    // instead of the usual JSON `CliInput`, for benchmarking against
+    // CVRPLIB instances without hand-converting each one.
+    let format = args.iter()
+        .position(|a| a == "--format")
+        .and_then(|i| args.get(i + 1))
         .map(|s| s.as_str())
-        .unwrap_or("haversine");
-    
-    let method = match distance_method {
-        "haversine" => DistanceMethod::Haversine,
-        "euclidean" => DistanceMethod::Euclidean,
-        "manhattan" => DistanceMethod::Manhattan,
-        _ => DistanceMethod::Haversine,
+        .unwrap_or("json");
+
+    let cli_input = if format == "tsplib" {
+        let algorithm = args.iter()
+            .position(|a| a == "--algorithm")
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+            .unwrap_or_else(|| "multi_start".to_string());
+
+        let instance = parse_tsplib_file(input_file)
+            .map_err(|e| format!("Failed to parse TSPLIB file: {}", e))?;
+
+        CliInput {
+            instance,
+            algorithm,
+            settings: None,
+            initial_solution: None,
+            precomputed_distance: true,
+        }
+    } else {
+        let input_data = fs::read_to_string(input_file)
+            .map_err(|e| format!("Failed to read input file: {}", e))?;
+
+        serde_json::from_str(&input_data)
+            .map_err(|e| format!("Failed to parse input JSON: {}", e))?
     };
-    
-    // Calculate distance matrix
-    calculate_distance_matrix(&mut instance, method);
-    
-    // Determine solver algorithm
-    let solver: Box<dyn VrpSolver + Sync> = match cli_input.algorithm.as_str() {
-        "greedy" => Box::new(GreedyNearestNeighbor::new()),
-        "greedy_farthest" => Box::new(GreedyNearestNeighbor::new().with_farthest_start(true)),
-        "clarke_wright" => {
-            let parallel = cli_input.settings
+
+    let (output, instance, solver_name) = solve_cli_input(cli_input)?;
+
+    if let Some(ref geo_json_file) = geo_json_file {
+        let geojson = routes_to_geojson(&output.routes, &instance);
+        let geojson_str = serde_json::to_string_pretty(&geojson)
+            .map_err(|e| format!("Failed to serialize GeoJSON: {}", e))?;
+        fs::write(geo_json_file, geojson_str)
+            .map_err(|e| format!("Failed to write GeoJSON file: {}", e))?;
+    }
+
+    // Write output file
+    let output_json = serde_json::to_string_pretty(&output)
+        .map_err(|e| format!("Failed to serialize output: {}", e))?;
+
+    fs::write(output_file, output_json)
+        .map_err(|e| format!("Failed to write output file: {}", e))?;
+
+    if !output.success {
+        eprintln!("VRP solving failed: {:?}", output.error);
+        std::process::exit(1);
+    }
+
+    println!("VRP solved successfully in {:.2}ms using {}", output.solve_time_ms, solver_name);
+
+    Ok(())
+}
+
+/// Solve a single `CliInput`, returning the resulting `CliOutput` alongside
+/// the fully-prepared `VrpInstance` (needed by callers that also want a
+/// GeoJSON export, which looks up stop coordinates from it).
+fn solve_cli_input(cli_input: CliInput) -> Result<(CliOutput, VrpInstance, &'static str), String> {
+    let algorithm = cli_input.algorithm.clone();
+
+    // Create VRP instance and calculate distance matrix
+    let mut instance = cli_input.instance;
+
+    // Alternate input formats (e.g. `--format tsplib`) already populate a
+    // correct distance matrix themselves; reinitializing and recomputing it
+    // geometrically here would discard that (e.g. TSPLIB's ATT/EXPLICIT
+    // weight types have no geometric equivalent).
+    if !cli_input.precomputed_distance {
+        // Reinitialize distance matrix with correct size
+        let n = instance.locations.len();
+        instance.distance_matrix = vec![vec![0.0; n]; n];
+
+        // A precomputed matrix file takes priority over geometric calculation,
+        // since it's typically real road-network distances (e.g. from OSRM).
+        let matrix_file = cli_input.settings.as_ref().and_then(|s| s.matrix_file.as_ref());
+
+        if let Some(matrix_file) = matrix_file {
+            let (distance_matrix, duration_matrix) = load_matrix_file(matrix_file)?;
+            set_distance_matrix(&mut instance, distance_matrix, duration_matrix)
+                .map_err(|e| format!("Invalid matrix file: {}", e))?;
+        } else {
+            // Determine distance calculation method
+            let distance_method = cli_input.settings
                 .as_ref()
-                .and_then(|s| s.parallel)
-                .unwrap_or(true);
-            Box::new(ClarkeWrightSavings::new().with_parallel(parallel))
-        },
-        "multi_start" => Box::new(MultiStartSolver::new().with_default_solvers()),
-        _ => Box::new(MultiStartSolver::new().with_default_solvers()),
-    };
-    
-    // Solve VRP
+                .and_then(|s| s.distance_method.as_ref())
+                .map(|s| s.as_str())
+                .unwrap_or("haversine");
+
+            let method = match distance_method {
+                "haversine" => DistanceMethod::Haversine,
+                "euclidean" => DistanceMethod::Euclidean,
+                "manhattan" => DistanceMethod::Manhattan,
+                _ => DistanceMethod::Haversine,
+            };
+
+            // Calculate distance matrix
+            calculate_distance_matrix(&mut instance, method);
+        }
+    }
+
+    // A feasible warm-start solution is used as-is: the solvers here have no
+    // improvement/local-search phase to refine a seed with, so re-solving
+    // from scratch would just throw away the caller's prior work.
+    let mut warm_start_warning: Option<String> = None;
+    let warm_start_solution = cli_input.initial_solution.as_ref().and_then(|routes| {
+        let violations = check_solution(&instance, routes);
+        if violations.is_empty() {
+            Some(routes_to_solution(routes))
+        } else {
+            warm_start_warning = Some(format!(
+                "Initial solution is infeasible ({} violation(s)); falling back to a cold solve",
+                violations.len()
+            ));
+            None
+        }
+    });
+
+    // `multi_start` with termination controls runs under a
+    // `TerminationController` instead of a single pass, so it needs the
+    // concrete `MultiStartSolver` rather than a `dyn VrpSolver` trait object.
+    let use_termination = algorithm == "multi_start"
+        && cli_input.settings.as_ref().map(|s| s.has_termination_controls()).unwrap_or(false);
+
     let start_time = std::time::Instant::now();
-    let result = solver.solve(&instance);
+    let (result, solver_name, termination_reason, iterations) = if let Some(solution) = warm_start_solution {
+        (Ok(solution), "warm_start", None, None)
+    } else if use_termination {
+        let config = cli_input.settings.as_ref().unwrap().to_termination_config();
+        let solver = MultiStartSolver::new().with_default_solvers();
+        match solver.solve_with_termination(&instance, config) {
+            Ok((solution, reason, iters)) => (Ok(solution), solver.name(), Some(reason), Some(iters)),
+            Err(e) => (Err(e), solver.name(), None, None),
+        }
+    } else {
+        // Determine solver algorithm
+        let solver: Box<dyn VrpSolver + Sync> = match algorithm.as_str() {
+            "greedy" => Box::new(GreedyNearestNeighbor::new()),
+            "greedy_farthest" => Box::new(GreedyNearestNeighbor::new().with_farthest_start(true)),
+            "clarke_wright" => {
+                let parallel = cli_input.settings
+                    .as_ref()
+                    .and_then(|s| s.parallel)
+                    .unwrap_or(true);
+                Box::new(ClarkeWrightSavings::new().with_parallel(parallel))
+            },
+            "multi_start" => Box::new(MultiStartSolver::new().with_default_solvers()),
+            _ => Box::new(MultiStartSolver::new().with_default_solvers()),
+        };
+        let result = solver.solve(&instance);
+        (result, solver.name(), None, None)
+    };
     let solve_time = start_time.elapsed().as_millis() as f64;
-    
+
     // Prepare output
     let output = match result {
         Ok(solution) => {
             let routes: Vec<RouteOutput> = solution.routes.into_iter().map(|route| {
+                let stops = route.locations.iter()
+                    .filter_map(|&id| instance.get_location(id))
+                    .map(|loc| StopOutput { id: loc.id, lat: loc.coordinate.lat, lon: loc.coordinate.lon })
+                    .collect();
+
                 RouteOutput {
                     vehicle_id: route.vehicle_id,
                     locations: route.locations,
+                    stops,
                     total_distance: route.total_distance,
                     total_duration: route.total_duration,
                     total_demand: route.total_demand,
@@ -122,10 +358,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 total_distance: solution.total_distance,
                 total_duration: solution.total_duration,
                 num_vehicles_used: solution.num_vehicles_used,
-                algorithm: cli_input.algorithm.clone(),
+                algorithm: algorithm.clone(),
                 solve_time_ms: solve_time,
                 success: true,
-                error: None,
+                error: warm_start_warning.clone(),
+                termination_reason,
+                iterations,
             }
         },
         Err(e) => CliOutput {
@@ -133,26 +371,347 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             total_distance: 0.0,
             total_duration: 0.0,
             num_vehicles_used: 0,
-            algorithm: cli_input.algorithm.clone(),
+            algorithm: algorithm.clone(),
             solve_time_ms: solve_time,
             success: false,
             error: Some(e.to_string()),
+            termination_reason,
+            iterations,
         },
     };
-    
-    // Write output file
-    let output_json = serde_json::to_string_pretty(&output)
-        .map_err(|e| format!("Failed to serialize output: {}", e))?;
-    
-    fs::write(output_file, output_json)
-        .map_err(|e| format!("Failed to write output file: {}", e))?;
-    
-    if !output.success {
-        eprintln!("VRP solving failed: {:?}", output.error);
-        std::process::exit(1);
+
+    Ok((output, instance, solver_name))
+}
+
+/// A `--serve` request: a `CliInput` tagged with a caller-chosen `id` so
+/// responses (which may complete out of order) can be matched back up.
+#[derive(Deserialize)]
+struct ServeRequest {
+    id: serde_json::Value,
+    #[serde(flatten)]
+    input: CliInput,
+}
+
+/// A `--serve` response, carrying the same `id` as its request.
+#[derive(Serialize)]
+struct ServeResponse {
+    id: serde_json::Value,
+    #[serde(flatten)]
+    output: CliOutput,
+}
+
+/// Run as a persistent daemon: read newline-delimited `ServeRequest`s from
+/// stdin and write the matching `ServeResponse` to stdout as each finishes.
+/// Requests are dispatched onto rayon's thread pool (the solvers are
+/// already `Sync`), so a slow instance doesn't block smaller ones queued
+/// after it; responses are written in completion order, not request order.
+fn run_serve() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{self, BufRead, Write};
+    use std::sync::mpsc;
+
+    let (tx, rx) = mpsc::channel::<String>();
+
+    // A single writer thread keeps stdout writes atomic and flushed per
+    // response, since multiple worker threads can't safely interleave writes.
+    let writer = std::thread::spawn(move || {
+        let stdout = io::stdout();
+        let mut handle = stdout.lock();
+        for line in rx {
+            let _ = writeln!(handle, "{}", line);
+            let _ = handle.flush();
+        }
+    });
+
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<ServeRequest>(&line) {
+            Ok(request) => {
+                let tx = tx.clone();
+                rayon::spawn(move || {
+                    let id = request.id.clone();
+                    let output = match solve_cli_input(request.input) {
+                        Ok((output, _instance, _solver_name)) => output,
+                        Err(e) => CliOutput {
+                            routes: Vec::new(),
+                            total_distance: 0.0,
+                            total_duration: 0.0,
+                            num_vehicles_used: 0,
+                            algorithm: String::new(),
+                            solve_time_ms: 0.0,
+                            success: false,
+                            error: Some(e),
+                            termination_reason: None,
+                            iterations: None,
+                        },
+                    };
+
+                    let response = ServeResponse { id, output };
+                    let serialized = serde_json::to_string(&response)
+                        .unwrap_or_else(|e| format!("{{\"error\":\"failed to serialize response: {}\"}}", e));
+                    let _ = tx.send(serialized);
+                });
+            }
+            Err(e) => {
+                let _ = tx.send(format!("{{\"error\":\"failed to parse request: {}\"}}", e));
+            }
+        }
     }
-    
-    println!("VRP solved successfully in {:.2}ms using {}", solve_time, solver.name());
-    
+
+    drop(tx);
+    let _ = writer.join();
     Ok(())
 }
+
+/// Validate a previously produced `CliOutput` against the `CliInput` it was
+/// solved from, independent of whichever solver produced it: every customer
+/// visited exactly once, no vehicle over capacity, routes grounded at the
+/// depot, and reported totals matching a fresh recomputation.
+fn run_check(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if args.len() < 6 || args[2] != "--input" || args[4] != "--solution" {
+        eprintln!("Usage: {} --check --input <input.json> --solution <output.json>", args[0]);
+        std::process::exit(1);
+    }
+
+    let input_file = &args[3];
+    let solution_file = &args[5];
+
+    let input_data = fs::read_to_string(input_file)
+        .map_err(|e| format!("Failed to read input file: {}", e))?;
+    let cli_input: CliInput = serde_json::from_str(&input_data)
+        .map_err(|e| format!("Failed to parse input JSON: {}", e))?;
+
+    let solution_data = fs::read_to_string(solution_file)
+        .map_err(|e| format!("Failed to read solution file: {}", e))?;
+    let cli_output: CliOutput = serde_json::from_str(&solution_data)
+        .map_err(|e| format!("Failed to parse solution JSON: {}", e))?;
+
+    let mut instance = cli_input.instance;
+    let n = instance.locations.len();
+    instance.distance_matrix = vec![vec![0.0; n]; n];
+    let method = match cli_input.settings.as_ref().and_then(|s| s.distance_method.as_deref()) {
+        Some("euclidean") => DistanceMethod::Euclidean,
+        Some("manhattan") => DistanceMethod::Manhattan,
+        _ => DistanceMethod::Haversine,
+    };
+    calculate_distance_matrix(&mut instance, method);
+
+    let violations = check_solution(&instance, &cli_output.routes);
+
+    if violations.is_empty() {
+        println!("Solution is feasible: {} route(s), {} location(s) checked", cli_output.routes.len(), n);
+        Ok(())
+    } else {
+        let report = serde_json::to_string_pretty(&violations)
+            .map_err(|e| format!("Failed to serialize violations: {}", e))?;
+        eprintln!("{}", report);
+        std::process::exit(1);
+    }
+}
+
+/// Rebuild a solver-native [`Solution`] from a caller-supplied warm start.
+fn routes_to_solution(routes: &[RouteOutput]) -> Solution {
+    let mut solution = Solution::new();
+    for route in routes {
+        solution.add_route(Route {
+            vehicle_id: route.vehicle_id,
+            locations: route.locations.clone(),
+            total_distance: route.total_distance,
+            total_duration: route.total_duration,
+            total_demand: route.total_demand,
+        });
+    }
+    solution
+}
+
+fn check_solution(instance: &VrpInstance, routes: &[RouteOutput]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    // Every non-depot customer must be visited exactly once.
+    let depot_ids: std::collections::HashSet<usize> = instance.vehicles.iter().map(|v| v.depot_id).collect();
+    let customer_ids: std::collections::HashSet<usize> = instance.locations.iter()
+        .map(|l| l.id)
+        .filter(|id| !depot_ids.contains(id))
+        .collect();
+
+    let mut visit_counts: std::collections::HashMap<usize, usize> = std::collections::HashMap::new();
+    for route in routes {
+        for &id in &route.locations {
+            *visit_counts.entry(id).or_insert(0) += 1;
+        }
+    }
+
+    for &customer_id in &customer_ids {
+        let count = visit_counts.get(&customer_id).copied().unwrap_or(0);
+        if count != 1 {
+            violations.push(Violation {
+                kind: "visit_count".to_string(),
+                vehicle_id: None,
+                location_id: Some(customer_id),
+                expected: Some(1.0),
+                actual: Some(count as f64),
+                message: format!("Customer {} was visited {} time(s), expected exactly once", customer_id, count),
+            });
+        }
+    }
+    for (&id, _) in visit_counts.iter().filter(|(id, _)| !customer_ids.contains(id)) {
+        violations.push(Violation {
+            kind: "unknown_location".to_string(),
+            vehicle_id: None,
+            location_id: Some(id),
+            expected: None,
+            actual: None,
+            message: format!("Location {} visited but is not a known customer", id),
+        });
+    }
+
+    for route in routes {
+        let Some(vehicle) = instance.get_vehicle(route.vehicle_id) else {
+            violations.push(Violation {
+                kind: "unknown_vehicle".to_string(),
+                vehicle_id: Some(route.vehicle_id),
+                location_id: None,
+                expected: None,
+                actual: None,
+                message: format!("Route references unknown vehicle {}", route.vehicle_id),
+            });
+            continue;
+        };
+
+        // Capacity
+        if route.total_demand > vehicle.capacity + CHECK_TOLERANCE {
+            violations.push(Violation {
+                kind: "capacity_exceeded".to_string(),
+                vehicle_id: Some(route.vehicle_id),
+                location_id: None,
+                expected: Some(vehicle.capacity),
+                actual: Some(route.total_demand),
+                message: format!(
+                    "Vehicle {} carries demand {} exceeding capacity {}",
+                    route.vehicle_id, route.total_demand, vehicle.capacity
+                ),
+            });
+        }
+
+        // Recompute distance/duration by walking depot -> stops -> depot
+        // through the fresh distance matrix, so the check is independent of
+        // whatever solver produced the reported totals.
+        let mut stop_ids = Vec::with_capacity(route.locations.len() + 2);
+        stop_ids.push(vehicle.depot_id);
+        stop_ids.extend(route.locations.iter().copied());
+        stop_ids.push(vehicle.depot_id);
+
+        let mut recomputed_distance = 0.0;
+        let mut recomputed_duration = 0.0;
+        for pair in stop_ids.windows(2) {
+            recomputed_distance += instance.get_distance(pair[0], pair[1]);
+            if let Some(time_matrix) = &instance.time_matrix {
+                recomputed_duration += time_matrix[pair[0]][pair[1]];
+            }
+        }
+
+        if (recomputed_distance - route.total_distance).abs() > CHECK_TOLERANCE.max(recomputed_distance * 0.01) {
+            violations.push(Violation {
+                kind: "distance_mismatch".to_string(),
+                vehicle_id: Some(route.vehicle_id),
+                location_id: None,
+                expected: Some(recomputed_distance),
+                actual: Some(route.total_distance),
+                message: format!(
+                    "Vehicle {} reported distance {} but recomputation gives {}",
+                    route.vehicle_id, route.total_distance, recomputed_distance
+                ),
+            });
+        }
+
+        if instance.time_matrix.is_some()
+            && (recomputed_duration - route.total_duration).abs() > CHECK_TOLERANCE.max(recomputed_duration * 0.01)
+        {
+            violations.push(Violation {
+                kind: "duration_mismatch".to_string(),
+                vehicle_id: Some(route.vehicle_id),
+                location_id: None,
+                expected: Some(recomputed_duration),
+                actual: Some(route.total_duration),
+                message: format!(
+                    "Vehicle {} reported duration {} but recomputation gives {}",
+                    route.vehicle_id, route.total_duration, recomputed_duration
+                ),
+            });
+        }
+    }
+
+    violations
+}
+
+/// Render solved routes as a GeoJSON `FeatureCollection`: one `LineString`
+/// per route (depot -> stops -> depot) plus one `Point` per visited stop.
+fn routes_to_geojson(routes: &[RouteOutput], instance: &VrpInstance) -> GeoJson {
+    let mut features = Vec::new();
+
+    for route in routes {
+        let depot = instance
+            .get_vehicle(route.vehicle_id)
+            .and_then(|v| instance.get_location(v.depot_id));
+
+        let mut coordinates = Vec::new();
+        if let Some(depot) = depot {
+            coordinates.push(vec![depot.coordinate.lon, depot.coordinate.lat]);
+        }
+        for stop in &route.stops {
+            coordinates.push(vec![stop.lon, stop.lat]);
+        }
+        if let Some(depot) = depot {
+            coordinates.push(vec![depot.coordinate.lon, depot.coordinate.lat]);
+        }
+
+        if coordinates.len() >= 2 {
+            let geometry = Geometry::new(Value::LineString(coordinates));
+
+            let mut properties = Map::new();
+            properties.insert("vehicle_id".to_string(), serde_json::Value::Number(route.vehicle_id.into()));
+            if let Some(distance_num) = serde_json::Number::from_f64(route.total_distance) {
+                properties.insert("total_distance".to_string(), serde_json::Value::Number(distance_num));
+            }
+            if let Some(duration_num) = serde_json::Number::from_f64(route.total_duration) {
+                properties.insert("total_duration".to_string(), serde_json::Value::Number(duration_num));
+            }
+            if let Some(demand_num) = serde_json::Number::from_f64(route.total_demand) {
+                properties.insert("total_demand".to_string(), serde_json::Value::Number(demand_num));
+            }
+
+            features.push(Feature {
+                bbox: None,
+                geometry: Some(geometry),
+                id: None,
+                properties: Some(properties),
+                foreign_members: None,
+            });
+        }
+
+        for stop in &route.stops {
+            let geometry = Geometry::new(Value::Point(vec![stop.lon, stop.lat]));
+            let mut properties = Map::new();
+            properties.insert("id".to_string(), serde_json::Value::Number(stop.id.into()));
+            properties.insert("vehicle_id".to_string(), serde_json::Value::Number(route.vehicle_id.into()));
+
+            features.push(Feature {
+                bbox: None,
+                geometry: Some(geometry),
+                id: None,
+                properties: Some(properties),
+                foreign_members: None,
+            });
+        }
+    }
+
+    GeoJson::FeatureCollection(FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    })
+}
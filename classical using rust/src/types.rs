@@ -24,6 +24,19 @@ pub struct Location {
     pub demand: f64,
     pub time_window: Option<TimeWindow>,
     pub service_time: f64, // Time required to service this location
+    /// Per-dimension demand (e.g. weight, volume) for multi-capacity CVRP
+    /// instances. `None` for plain single-commodity locations; when set,
+    /// `demand` holds the dimensions' sum so scalar capacity checks still work.
+    #[serde(default)]
+    pub demand_dims: Option<Vec<f64>>,
+    /// Links this stop to its paired pickup or delivery request; both ends of
+    /// a pair share the same id. `None` for plain single-visit customers.
+    #[serde(default)]
+    pub pickup_pair_id: Option<usize>,
+    /// Cost charged if a solver skips this stop instead of visiting it.
+    /// `None` means the stop is mandatory.
+    #[serde(default)]
+    pub drop_penalty: Option<f64>,
 }
 
 impl Location {
@@ -42,6 +55,9 @@ impl Location {
             demand,
             time_window,
             service_time,
+            demand_dims: None,
+            pickup_pair_id: None,
+            drop_penalty: None,
         }
     }
 
@@ -49,6 +65,30 @@ impl Location {
     pub fn depot(id: usize, name: String, coordinate: Coordinate) -> Self {
         Self::new(id, name, coordinate, 0.0, None, 0.0)
     }
+
+    /// Attach per-dimension demand, e.g. `[weight, volume]`. `demand` is
+    /// overwritten with the dimensions' sum so scalar capacity checks keep
+    /// working against the combined load.
+    pub fn with_demand_dims(mut self, demand_dims: Vec<f64>) -> Self {
+        self.demand = demand_dims.iter().sum();
+        self.demand_dims = Some(demand_dims);
+        self
+    }
+
+    /// Marks this stop as one half of a linked pickup/delivery request;
+    /// both halves must share `pair_id` and a solver honoring pairing keeps
+    /// the pickup ahead of its delivery on the same route.
+    pub fn with_pickup_pair_id(mut self, pair_id: usize) -> Self {
+        self.pickup_pair_id = Some(pair_id);
+        self
+    }
+
+    /// Makes this stop droppable: a solver may skip it instead of visiting,
+    /// at the cost of `penalty`.
+    pub fn with_drop_penalty(mut self, penalty: f64) -> Self {
+        self.drop_penalty = Some(penalty);
+        self
+    }
 }
 
 /// Time window constraint for a location
@@ -68,6 +108,36 @@ impl TimeWindow {
     }
 }
 
+/// Travel mode a vehicle uses, driving which OSM highway tags it may route
+/// over (see `OsmParser::filter_roads_for_profile`) and its default average
+/// speed when no road-network timing is available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VehicleProfile {
+    Driving,
+    Cycling,
+    Walking,
+}
+
+impl VehicleProfile {
+    /// Default average speed (meters/second) for this profile, used when
+    /// building an instance's distance/time matrices from straight-line
+    /// distance rather than real road-network timing.
+    pub fn default_average_speed_ms(&self) -> f64 {
+        match self {
+            VehicleProfile::Driving => 14.0,  // ~50 km/h
+            VehicleProfile::Cycling => 4.0,   // ~14 km/h
+            VehicleProfile::Walking => 1.4,   // ~5 km/h
+        }
+    }
+}
+
+impl Default for VehicleProfile {
+    fn default() -> Self {
+        VehicleProfile::Driving
+    }
+}
+
 /// Vehicle definition with constraints
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vehicle {
@@ -76,6 +146,26 @@ pub struct Vehicle {
     pub max_distance: Option<f64>,
     pub max_duration: Option<f64>,
     pub depot_id: usize,
+    #[serde(default)]
+    pub profile: VehicleProfile,
+    /// Per-dimension capacity (e.g. weight, volume) for multi-capacity CVRP
+    /// instances. `None` for plain single-commodity vehicles; when set,
+    /// `capacity` holds the dimensions' sum so scalar capacity checks still work.
+    #[serde(default)]
+    pub capacity_dims: Option<Vec<f64>>,
+    /// Mandatory driver break (e.g. hours-of-service rest) required somewhere
+    /// on this vehicle's route. `None` for vehicles with no break requirement.
+    #[serde(default)]
+    pub break_rule: Option<Break>,
+}
+
+/// A mandatory driver break that must be scheduled within `[earliest, latest]`
+/// of the route's elapsed time, lasting `duration`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Break {
+    pub earliest: f64,
+    pub latest: f64,
+    pub duration: f64,
 }
 
 impl Vehicle {
@@ -92,8 +182,26 @@ impl Vehicle {
             max_distance,
             max_duration,
             depot_id,
+            profile: VehicleProfile::default(),
+            capacity_dims: None,
+            break_rule: None,
         }
     }
+
+    /// Attach per-dimension capacity, e.g. `[max_weight, max_volume]`.
+    /// `capacity` is overwritten with the dimensions' sum so scalar capacity
+    /// checks keep working against the combined limit.
+    pub fn with_capacity_dims(mut self, capacity_dims: Vec<f64>) -> Self {
+        self.capacity = capacity_dims.iter().sum();
+        self.capacity_dims = Some(capacity_dims);
+        self
+    }
+
+    /// Require a mandatory driver break somewhere on this vehicle's route.
+    pub fn with_break(mut self, break_rule: Break) -> Self {
+        self.break_rule = Some(break_rule);
+        self
+    }
 }
 
 /// A route for a single vehicle
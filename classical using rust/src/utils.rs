@@ -178,6 +178,61 @@ impl VrpInstanceBuilder {
         self
     }
 
+    /// Like [`add_customer`](Self::add_customer), but `demand_dims` (e.g.
+    /// `[weight, volume]`) drives capacity checks instead of a single scalar.
+    pub fn add_customer_multi_dim(
+        mut self,
+        id: usize,
+        name: String,
+        coordinate: Coordinate,
+        demand_dims: Vec<f64>,
+        time_window: Option<TimeWindow>,
+        service_time: f64,
+    ) -> Self {
+        let location = Location::new(id, name, coordinate, 0.0, time_window, service_time)
+            .with_demand_dims(demand_dims);
+        self.locations.push(location);
+        self
+    }
+
+    /// Like [`add_customer`](Self::add_customer), but links this stop to
+    /// `pair_id`'s other half (a pickup or a delivery) for pickup-and-delivery
+    /// instances. `demand` carries the sign: positive to load, negative to unload.
+    pub fn add_paired_customer(
+        mut self,
+        id: usize,
+        name: String,
+        coordinate: Coordinate,
+        demand: f64,
+        pair_id: usize,
+        time_window: Option<TimeWindow>,
+        service_time: f64,
+    ) -> Self {
+        let location = Location::new(id, name, coordinate, demand, time_window, service_time)
+            .with_pickup_pair_id(pair_id);
+        self.locations.push(location);
+        self
+    }
+
+    /// Like [`add_customer`](Self::add_customer), but the stop may be
+    /// skipped by a solver that honors `drop_penalty` in exchange for paying
+    /// `penalty` instead of visiting it.
+    pub fn add_optional_customer(
+        mut self,
+        id: usize,
+        name: String,
+        coordinate: Coordinate,
+        demand: f64,
+        penalty: f64,
+        time_window: Option<TimeWindow>,
+        service_time: f64,
+    ) -> Self {
+        let location = Location::new(id, name, coordinate, demand, time_window, service_time)
+            .with_drop_penalty(penalty);
+        self.locations.push(location);
+        self
+    }
+
     pub fn add_vehicle(mut self, vehicle: Vehicle) -> Self {
         self.vehicles.push(vehicle);
         self
@@ -193,6 +248,21 @@ impl VrpInstanceBuilder {
         self
     }
 
+    /// Like [`add_vehicle_simple`](Self::add_vehicle_simple), but
+    /// `capacity_dims` (e.g. `[max_weight, max_volume]`) drives capacity
+    /// checks instead of a single scalar.
+    pub fn add_vehicle_multi_dim(
+        mut self,
+        id: usize,
+        capacity_dims: Vec<f64>,
+        depot_id: usize,
+    ) -> Self {
+        let vehicle = Vehicle::new(id, 0.0, None, None, depot_id)
+            .with_capacity_dims(capacity_dims);
+        self.vehicles.push(vehicle);
+        self
+    }
+
     pub fn build(self) -> VrpResult<VrpInstance> {
         if self.locations.is_empty() {
             return Err(VrpError::InvalidInput("No locations provided".to_string()));
@@ -262,14 +332,41 @@ pub fn load_solution_from_json<P: AsRef<Path>>(path: P) -> VrpResult<Solution> {
 pub fn save_solution_to_json<P: AsRef<Path>>(solution: &Solution, path: P) -> VrpResult<()> {
     let file = File::create(&path)
         .map_err(|e| VrpError::InvalidInput(format!("Cannot create file {:?}: {}", path.as_ref(), e)))?;
-    
+
     let writer = BufWriter::new(file);
     serde_json::to_writer_pretty(writer, solution)
         .map_err(|e| VrpError::InvalidInput(format!("Cannot write JSON: {}", e)))?;
-    
+
     Ok(())
 }
 
+/// Save a solution, picking the compact `bincode` encoding when `path` ends
+/// in `.bin` and JSON (via [`save_solution_to_json`]) otherwise.
+pub fn save_solution<P: AsRef<Path>>(solution: &Solution, path: P) -> VrpResult<()> {
+    if path.as_ref().extension().and_then(|ext| ext.to_str()) == Some("bin") {
+        let file = File::create(&path)
+            .map_err(|e| VrpError::InvalidInput(format!("Cannot create file {:?}: {}", path.as_ref(), e)))?;
+        bincode::serialize_into(BufWriter::new(file), solution)
+            .map_err(|e| VrpError::InvalidInput(format!("Cannot write binary solution: {}", e)))?;
+        Ok(())
+    } else {
+        save_solution_to_json(solution, path)
+    }
+}
+
+/// Load a solution previously written by [`save_solution`], auto-detecting
+/// the binary form from a `.bin` extension and falling back to JSON otherwise.
+pub fn load_solution<P: AsRef<Path>>(path: P) -> VrpResult<Solution> {
+    if path.as_ref().extension().and_then(|ext| ext.to_str()) == Some("bin") {
+        let file = File::open(&path)
+            .map_err(|e| VrpError::InvalidInput(format!("Cannot open file {:?}: {}", path.as_ref(), e)))?;
+        bincode::deserialize_from(BufReader::new(file))
+            .map_err(|e| VrpError::InvalidInput(format!("Cannot parse binary solution: {}", e)))
+    } else {
+        load_solution_from_json(path)
+    }
+}
+
 /// Create a solution summary string
 pub fn format_solution_summary(solution: &Solution) -> String {
     format!(
@@ -1,3 +1,7 @@
+//! Standalone OSM PBF converter. Superseded by `optiqroute convert`, which
+//! covers the same PBF-to-JSON/GeoJSON path and can feed straight into
+//! `optiqroute generate` without writing the JSON to disk first.
+
 use clap::{Arg, Command};
 use vrp_solver::osm_parser::OsmParser;
 
@@ -30,6 +34,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .help("Output GeoJSON file path")
                 .required(false),
         )
+        .arg(
+            Arg::new("bin")
+                .short('b')
+                .long("bin")
+                .value_name("FILE")
+                .help("Output compact binary (bincode) file path, for large extracts where JSON parsing dominates load time")
+                .required(false),
+        )
         .arg(
             Arg::new("roads-only")
                 .short('r')
@@ -42,6 +54,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let input_file = matches.get_one::<String>("input").unwrap();
     let json_file = matches.get_one::<String>("json");
     let geojson_file = matches.get_one::<String>("geojson");
+    let bin_file = matches.get_one::<String>("bin");
     let roads_only = matches.get_flag("roads-only");
 
     println!("🚀 Starting OSM conversion process...");
@@ -72,8 +85,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         parser.export_to_geojson(geojson_path)?;
     }
 
-    // If neither output format was specified, export to default names
-    if json_file.is_none() && geojson_file.is_none() {
+    // Export to binary if requested
+    if let Some(bin_path) = bin_file {
+        println!("📦 Exporting to binary...");
+        parser.export_to_binary(bin_path)?;
+    }
+
+    // If no output format was specified, export to default names
+    if json_file.is_none() && geojson_file.is_none() && bin_file.is_none() {
         let base_name = input_file
             .strip_suffix(".osm.pbf")
             .unwrap_or(input_file)
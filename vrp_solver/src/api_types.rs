@@ -1,7 +1,10 @@
 //! API request and response types for the VRP web server
 
 use crate::types::*;
+use crate::diff::SolutionDiff;
+use crate::job::{JobProgress, JobStatus};
 use crate::osm_parser::OsmData;
+use crate::termination::TerminationConfig;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -66,7 +69,7 @@ pub struct MapLocationResponse {
     pub mapped_customers: Vec<MappedLocation>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MappedLocation {
     pub node_id: i64,
     pub lat: f64,
@@ -80,7 +83,22 @@ pub struct GenerateVrpRequest {
     pub graph_id: Uuid,
     pub vehicles: usize,
     pub capacity: f64,
+    /// Per-dimension capacity (e.g. `[weight, volume]`) for a multi-commodity
+    /// fleet; when set, overrides `capacity` on each generated vehicle via
+    /// `Vehicle::with_capacity_dims` (which also recomputes the scalar
+    /// `capacity` as the dimensions' sum). `None` keeps plain scalar capacity.
+    pub capacity_dims: Option<Vec<f64>>,
     pub constraints: VrpConstraints,
+    /// `"road"` routes locations over the graph's real road network
+    /// (Dijkstra over `RoadGraph`); anything else, including omission,
+    /// falls back to straight-line haversine distance.
+    pub distance: Option<String>,
+    /// Travel mode for the fleet, defaulting to `driving` when omitted.
+    /// Drives the default average speed used to build the distance/time
+    /// matrices (see `VehicleProfile::default_average_speed_ms`); the
+    /// graph's road network is filtered for this profile separately, at
+    /// `POST /osm/upload` time.
+    pub profile: Option<VehicleProfile>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -99,11 +117,65 @@ pub struct GenerateVrpResponse {
     pub depot_count: usize,
 }
 
+// Precomputed Distance/Duration Matrix API Types
+#[derive(Debug, Deserialize)]
+pub struct SetDistanceMatrixRequest {
+    pub vrp_id: Uuid,
+    pub distance_matrix: Vec<Vec<f64>>,
+    pub duration_matrix: Option<Vec<Vec<f64>>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetDistanceMatrixResponse {
+    pub vrp_id: Uuid,
+    pub size: usize,
+    pub has_duration_matrix: bool,
+    pub message: String,
+}
+
 // VRP Solving API Types
 #[derive(Debug, Deserialize)]
 pub struct SolveVrpRequest {
     pub vrp_id: Uuid,
     pub algorithm: SolverAlgorithm,
+    /// When set (only meaningful for `SolverAlgorithm::MultiStart`), runs the
+    /// solver repeatedly until one of these criteria fires instead of a
+    /// single pass.
+    pub termination: Option<TerminationSettings>,
+    /// Generation budget for `SolverAlgorithm::Metaheuristic`; ignored by
+    /// every other algorithm.
+    pub max_generations: Option<usize>,
+    /// Wall-clock budget (milliseconds) for `SolverAlgorithm::Metaheuristic`.
+    pub max_time_ms: Option<u64>,
+    /// Coefficient-of-variation convergence threshold for
+    /// `SolverAlgorithm::Metaheuristic`: stops once the best objective's CV
+    /// over the last 200 generations drops below this.
+    pub min_cv: Option<f64>,
+    /// When `true`, the solve runs on a background task and this request
+    /// returns a `job_id` immediately instead of waiting for a solution;
+    /// poll `GET /vrp/job/:job_id` for progress and `DELETE` it to cancel.
+    #[serde(rename = "async", default)]
+    pub async_mode: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TerminationSettings {
+    pub max_iterations: Option<usize>,
+    pub max_time_secs: Option<f64>,
+    pub min_cv: Option<f64>,
+    pub window_size: Option<usize>,
+}
+
+impl From<TerminationSettings> for TerminationConfig {
+    fn from(settings: TerminationSettings) -> Self {
+        let defaults = TerminationConfig::default();
+        Self {
+            max_iterations: settings.max_iterations.or(defaults.max_iterations),
+            max_time_secs: settings.max_time_secs.or(defaults.max_time_secs),
+            min_cv: settings.min_cv.or(defaults.min_cv),
+            window_size: settings.window_size.unwrap_or(defaults.window_size),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -113,6 +185,8 @@ pub enum SolverAlgorithm {
     GreedyFarthest,
     ClarkeWright,
     MultiStart,
+    Metaheuristic,
+    SimulatedAnnealing,
 }
 
 #[derive(Debug, Serialize)]
@@ -124,6 +198,25 @@ pub struct SolveVrpResponse {
     pub total_duration: f64,
     pub vehicles_used: usize,
     pub solve_time_ms: f64,
+    /// Present for `MultiStart` when `termination` settings were supplied in
+    /// the request, and always for `Metaheuristic`; `None` otherwise.
+    pub termination_reason: Option<crate::termination::TerminationReason>,
+    pub iterations: Option<usize>,
+}
+
+// Async solve job API Types
+#[derive(Debug, Serialize)]
+pub struct SolveJobQueuedResponse {
+    pub job_id: Uuid,
+    pub status: JobStatus,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobStatusResponse {
+    pub status: JobStatus,
+    pub progress: JobProgress,
+    pub solution_id: Option<Uuid>,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -144,12 +237,54 @@ pub struct ApiLocation {
     pub lon: f64,
     pub demand: f64,
     pub service_time: f64,
+    /// This stop's arrival/departure time from `RouteValidator::compute_schedule`;
+    /// `None` when the instance has no time matrix to schedule against.
+    pub arrival: Option<f64>,
+    pub departure: Option<f64>,
 }
 
 // Solution Export API Types
 #[derive(Debug, Deserialize)]
 pub struct ExportFormat {
-    pub format: Option<String>, // "geojson", "json", "csv"
+    pub format: Option<String>, // "geojson", "json", "csv", "osrm"
+    /// Meaningful when `format` is `"geojson"` or `"osrm"`. `"roads"` follows
+    /// the real road network (via the VRP instance's mapped `StoredGraph`)
+    /// instead of straight depot-stop-depot lines.
+    pub geometry: Option<String>,
+    /// Only meaningful when `format` is `"osrm"`. Decimal digits for the
+    /// encoded polyline geometry; defaults to 5 (OSRM's own default), with 6
+    /// available for clients that expect the higher-precision variant.
+    pub precision: Option<u32>,
+}
+
+// Solution diffing API Types
+#[derive(Debug, Deserialize)]
+pub struct DiffSolutionsRequest {
+    pub vrp_id: Uuid,
+    pub old_solution_id: Uuid,
+    pub new_solution_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiffSolutionsResponse {
+    pub old_solution_id: Uuid,
+    pub new_solution_id: Uuid,
+    pub diff: SolutionDiff,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OsrmExportQuery {
+    /// Spacing, in meters, between interpolated points along each leg's
+    /// great-circle geometry. Defaults to 50m when omitted.
+    pub step_meters: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppStateStats {
+    pub graphs: usize,
+    pub mappings: usize,
+    pub vrp_instances: usize,
+    pub solutions: usize,
 }
 
 #[derive(Debug, Serialize)]
@@ -178,33 +313,36 @@ impl ErrorResponse {
 }
 
 // Internal state structures
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredGraph {
     pub id: Uuid,
     pub osm_data: OsmData,
+    #[serde(with = "timestamp_serde")]
     pub created_at: std::time::SystemTime,
     pub node_count: usize,
     pub way_count: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredMapping {
     pub graph_id: Uuid,
     pub depot: MappedLocation,
     pub customers: Vec<MappedLocation>,
+    #[serde(with = "timestamp_serde")]
     pub created_at: std::time::SystemTime,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredVrpInstance {
     pub id: Uuid,
     pub mapping: StoredMapping,
     pub instance: VrpInstance,
     pub constraints: VrpConstraints,
+    #[serde(with = "timestamp_serde")]
     pub created_at: std::time::SystemTime,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredSolution {
     pub id: Uuid,
     pub vrp_id: Uuid,
@@ -241,6 +379,8 @@ impl From<&Location> for ApiLocation {
             lon: location.coordinate.lon,
             demand: location.demand,
             service_time: location.service_time,
+            arrival: None,
+            departure: None,
         }
     }
 }
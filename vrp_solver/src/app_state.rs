@@ -0,0 +1,240 @@
+//! Application state management for the VRP web server
+
+use crate::api_types::*;
+use crate::job::SolveJob;
+use crate::routing::RoadGraph;
+use crate::store::{InMemoryStore, SledStore, Store};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+pub use crate::api_types::AppStateStats;
+
+/// Thread-safe application state, boxed over a pluggable [`Store`] backend
+/// so the same handlers work whether entities live in memory or on disk.
+#[derive(Clone)]
+pub struct AppState {
+    store: Arc<dyn Store>,
+    // Cache of built road-network adjacency graphs, keyed by the same UUID
+    // as the `StoredGraph` they were built from, so routing doesn't need to
+    // rebuild the graph on every request. This lives outside `Store` since
+    // it's a derived, rebuildable cache rather than persisted state.
+    pub road_graphs: Arc<RwLock<HashMap<Uuid, Arc<RoadGraph>>>>,
+    // Cache of road-snapped route geometry (one coordinate list per route),
+    // keyed by solution id, so repeated `?geometry=roads` exports don't
+    // re-walk the road graph. Also a derived cache, not persisted state.
+    solution_geometry: Arc<RwLock<HashMap<Uuid, Arc<Vec<Vec<[f64; 2]>>>>>>,
+    // Background solve jobs started via `POST /vrp/solve` with `async: true`,
+    // keyed by job id. Purely runtime state: a job's outcome is persisted as
+    // a normal `StoredSolution` once it finishes, so it doesn't belong in `Store`.
+    jobs: Arc<RwLock<HashMap<Uuid, Arc<SolveJob>>>>,
+}
+
+impl AppState {
+    /// Create application state backed by the original in-memory store.
+    pub fn new() -> Self {
+        Self::with_store(Arc::new(InMemoryStore::new()))
+    }
+
+    /// Create application state backed by a disk-persisted `sled` database
+    /// at `path`, so graphs/instances/solutions survive a restart.
+    pub fn with_sled_store(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        Ok(Self::with_store(Arc::new(SledStore::open(path)?)))
+    }
+
+    /// Create application state backed by any [`Store`] implementation.
+    pub fn with_store(store: Arc<dyn Store>) -> Self {
+        Self {
+            store,
+            road_graphs: Arc::new(RwLock::new(HashMap::new())),
+            solution_geometry: Arc::new(RwLock::new(HashMap::new())),
+            jobs: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    // Graph operations
+    pub fn store_graph(&self, graph: StoredGraph) -> Result<Uuid, String> {
+        self.store.store_graph(graph)
+    }
+
+    pub fn get_graph(&self, graph_id: &Uuid) -> Result<Option<StoredGraph>, String> {
+        self.store.get_graph(graph_id)
+    }
+
+    pub fn list_graphs(&self) -> Result<Vec<Uuid>, String> {
+        self.store.list_graphs()
+    }
+
+    // Mapping operations
+    pub fn store_mapping(&self, mapping: StoredMapping) -> Result<(), String> {
+        self.store.store_mapping(mapping)
+    }
+
+    pub fn get_mapping(&self, graph_id: &Uuid) -> Result<Option<StoredMapping>, String> {
+        self.store.get_mapping(graph_id)
+    }
+
+    // VRP instance operations
+    pub fn store_vrp_instance(&self, instance: StoredVrpInstance) -> Result<Uuid, String> {
+        self.store.store_vrp_instance(instance)
+    }
+
+    pub fn get_vrp_instance(&self, vrp_id: &Uuid) -> Result<Option<StoredVrpInstance>, String> {
+        self.store.get_vrp_instance(vrp_id)
+    }
+
+    pub fn list_vrp_instances(&self) -> Result<Vec<Uuid>, String> {
+        self.store.list_vrp_instances()
+    }
+
+    // Road network graph operations
+    /// Return the cached road-network graph for `graph_id`, building and
+    /// caching it from the stored OSM data if this is the first request for it.
+    pub fn get_or_build_road_graph(&self, graph_id: &Uuid) -> Result<Arc<RoadGraph>, String> {
+        if let Some(cached) = self.road_graphs.read()
+            .map_err(|_| "Failed to acquire read lock for road graphs".to_string())?
+            .get(graph_id)
+        {
+            return Ok(cached.clone());
+        }
+
+        let stored_graph = self.get_graph(graph_id)?
+            .ok_or_else(|| format!("Graph {} not found", graph_id))?;
+
+        let road_graph = Arc::new(RoadGraph::build(&stored_graph.osm_data));
+
+        self.road_graphs.write()
+            .map_err(|_| "Failed to acquire write lock for road graphs".to_string())?
+            .insert(*graph_id, road_graph.clone());
+
+        Ok(road_graph)
+    }
+
+    /// Return the cached road-snapped geometry for `solution_id`, computing
+    /// and caching it via `compute` on first request.
+    pub fn get_or_build_road_geometry(
+        &self,
+        solution_id: &Uuid,
+        compute: impl FnOnce() -> Result<Vec<Vec<[f64; 2]>>, String>,
+    ) -> Result<Arc<Vec<Vec<[f64; 2]>>>, String> {
+        if let Some(cached) = self.solution_geometry.read()
+            .map_err(|_| "Failed to acquire read lock for solution geometry cache".to_string())?
+            .get(solution_id)
+        {
+            return Ok(cached.clone());
+        }
+
+        let geometry = Arc::new(compute()?);
+
+        self.solution_geometry.write()
+            .map_err(|_| "Failed to acquire write lock for solution geometry cache".to_string())?
+            .insert(*solution_id, geometry.clone());
+
+        Ok(geometry)
+    }
+
+    // Background solve job operations
+    /// Register a new queued solve job and return its id.
+    pub fn create_job(&self) -> Result<(Uuid, Arc<SolveJob>), String> {
+        let job_id = Uuid::new_v4();
+        let job = Arc::new(SolveJob::new());
+
+        self.jobs.write()
+            .map_err(|_| "Failed to acquire write lock for jobs".to_string())?
+            .insert(job_id, job.clone());
+
+        Ok((job_id, job))
+    }
+
+    pub fn get_job(&self, job_id: &Uuid) -> Result<Option<Arc<SolveJob>>, String> {
+        Ok(self.jobs.read()
+            .map_err(|_| "Failed to acquire read lock for jobs".to_string())?
+            .get(job_id)
+            .cloned())
+    }
+
+    // Solution operations
+    pub fn store_solution(&self, solution: StoredSolution) -> Result<Uuid, String> {
+        self.store.store_solution(solution)
+    }
+
+    pub fn get_solution(&self, solution_id: &Uuid) -> Result<Option<StoredSolution>, String> {
+        self.store.get_solution(solution_id)
+    }
+
+    pub fn list_solutions(&self) -> Result<Vec<Uuid>, String> {
+        self.store.list_solutions()
+    }
+
+    pub fn get_solutions_for_vrp(&self, vrp_id: &Uuid) -> Result<Vec<StoredSolution>, String> {
+        self.store.get_solutions_for_vrp(vrp_id)
+    }
+
+    // Cleanup operations (for memory/disk management)
+    pub fn cleanup_old_data(&self, max_age_hours: u64) -> Result<(), String> {
+        self.store.cleanup_old_data(max_age_hours)?;
+
+        // Drop cached road graphs whose source graph was just evicted
+        let live_graph_ids: std::collections::HashSet<Uuid> = self.list_graphs()?.into_iter().collect();
+        if let Ok(mut road_graphs) = self.road_graphs.write() {
+            road_graphs.retain(|graph_id, _| live_graph_ids.contains(graph_id));
+        }
+
+        // Likewise for cached road-snapped geometry of evicted solutions
+        let live_solution_ids: std::collections::HashSet<Uuid> = self.list_solutions()?.into_iter().collect();
+        if let Ok(mut solution_geometry) = self.solution_geometry.write() {
+            solution_geometry.retain(|solution_id, _| live_solution_ids.contains(solution_id));
+        }
+
+        Ok(())
+    }
+
+    // Statistics
+    pub fn get_stats(&self) -> Result<AppStateStats, String> {
+        self.store.get_stats()
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osm_parser::OsmData;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_app_state_operations() {
+        let state = AppState::new();
+        let graph_id = Uuid::new_v4();
+
+        // Test storing and retrieving a graph
+        let graph = StoredGraph {
+            id: graph_id,
+            osm_data: OsmData {
+                nodes: HashMap::new(),
+                ways: HashMap::new(),
+            },
+            created_at: std::time::SystemTime::now(),
+            node_count: 0,
+            way_count: 0,
+        };
+
+        // Store graph
+        let stored_id = state.store_graph(graph.clone()).unwrap();
+        assert_eq!(stored_id, graph_id);
+
+        // Retrieve graph
+        let retrieved = state.get_graph(&graph_id).unwrap();
+        assert!(retrieved.is_some());
+        assert_eq!(retrieved.unwrap().id, graph_id);
+
+        // Test stats
+        let stats = state.get_stats().unwrap();
+        assert_eq!(stats.graphs, 1);
+    }
+}
@@ -0,0 +1,794 @@
+//! Unified `optiqroute` CLI: `convert`, `generate`, `solve` and `export`
+//! subcommands replacing the old per-purpose binaries. Each subcommand reads
+//! its primary JSON input from a file, or from stdin when the file argument
+//! is omitted or given as `-`, and writes its result to a file or stdout the
+//! same way, so two stages can be chained with a shell pipe without an
+//! intermediate file ever touching disk:
+//!
+//!   optiqroute generate --osm-json city.json | optiqroute solve --instance - --output solution.json
+//!
+//! Run `optiqroute <subcommand> --help` for a subcommand's full argument list.
+
+use clap::{Arg, ArgMatches, Command};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use serde_json;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use vrp_solver::{
+    calculate_distance_matrix,
+    distance::DistanceMethod,
+    export::{solution_to_geojson, solution_to_geojson_with_road_geometry},
+    osm_parser::{load_osm_data, GraphSource, NodeSpatialIndex, OsmData, OsmParser, PlaceNameIndex, PostgisSource},
+    solver::{ClarkeWrightSavings, GreedyNearestNeighbor, MultiStartSolver, VrpSolver},
+    types::{Coordinate, Location, Solution, TimeWindow, VrpInstance},
+    utils::{save_instance_to_json, VrpInstanceBuilder},
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = Command::new("optiqroute")
+        .version("1.0")
+        .author("VRP Solver")
+        .about("OSM to VRP pipeline: convert road data, generate instances, solve, export")
+        .subcommand_required(true)
+        .subcommand(convert_command())
+        .subcommand(generate_command())
+        .subcommand(solve_command())
+        .subcommand(export_command())
+        .get_matches();
+
+    match matches.subcommand() {
+        Some(("convert", sub)) => run_convert(sub),
+        Some(("generate", sub)) => run_generate(sub),
+        Some(("solve", sub)) => run_solve(sub),
+        Some(("export", sub)) => run_export(sub),
+        _ => unreachable!("subcommand_required(true) guarantees one of the above"),
+    }
+}
+
+/// Reads `path` when given and not `-`, otherwise all of stdin.
+fn read_input(path: Option<&String>) -> Result<String, Box<dyn std::error::Error>> {
+    match path.map(|s| s.as_str()) {
+        Some(path) if path != "-" => Ok(std::fs::read_to_string(path)?),
+        _ => {
+            let mut buf = String::new();
+            io::stdin().read_to_string(&mut buf)?;
+            Ok(buf)
+        }
+    }
+}
+
+/// Reads OSM data from `path`, transparently accepting either the compact
+/// `.bin` (bincode) form or JSON (from a file, '-', or omitted stdin) — the
+/// same auto-detection [`load_osm_data`] does, extended to stdin for JSON.
+fn read_osm_data(path: Option<&String>) -> Result<OsmData, Box<dyn std::error::Error>> {
+    match path.map(|s| s.as_str()) {
+        Some(path) if path != "-" && path.ends_with(".bin") => load_osm_data(path),
+        _ => Ok(serde_json::from_str(&read_input(path)?)?),
+    }
+}
+
+/// Writes `value` as pretty JSON to `path` when given and not `-`, otherwise to stdout.
+fn write_output<T: Serialize>(path: Option<&String>, value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(value)?;
+    match path.map(|s| s.as_str()) {
+        Some(path) if path != "-" => {
+            std::fs::write(path, json)?;
+            eprintln!("✅ Wrote {}", path);
+        }
+        _ => {
+            io::stdout().write_all(json.as_bytes())?;
+            io::stdout().write_all(b"\n")?;
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------
+// convert: OSM PBF -> OsmData JSON (optionally GeoJSON), via OsmParser
+// ---------------------------------------------------------------------
+
+fn convert_command() -> Command {
+    Command::new("convert")
+        .about("Parses an OSM PBF file into OsmData JSON (and optionally GeoJSON)")
+        .arg(Arg::new("input").short('i').long("input").value_name("FILE")
+            .help("Input PBF file path").required(true))
+        .arg(Arg::new("output").short('o').long("output").value_name("FILE")
+            .help("Output OsmData file ('-' or omitted writes JSON to stdout; a '.bin' extension \
+                   writes the compact bincode form instead of JSON)"))
+        .arg(Arg::new("geojson").short('g').long("geojson").value_name("FILE")
+            .help("Optional output GeoJSON file for the parsed road network"))
+        .arg(Arg::new("roads_only").short('r').long("roads-only")
+            .help("Filter to roads/highways only").action(clap::ArgAction::SetTrue))
+}
+
+fn run_convert(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let input_file = matches.get_one::<String>("input").unwrap();
+    let output_file = matches.get_one::<String>("output");
+    let geojson_file = matches.get_one::<String>("geojson");
+    let roads_only = matches.get_flag("roads_only");
+
+    let mut parser = OsmParser::new();
+    parser.parse_pbf_file(input_file)?;
+
+    if roads_only {
+        parser.filter_roads_only();
+    }
+
+    if let Some(geojson_path) = geojson_file {
+        parser.export_to_geojson(geojson_path)?;
+        eprintln!("🌍 Wrote {}", geojson_path);
+    }
+
+    match output_file.filter(|p| p.as_str() != "-") {
+        Some(path) if path.ends_with(".bin") => {
+            parser.export_to_binary(path)?;
+            eprintln!("✅ Wrote {}", path);
+        }
+        _ => write_output(output_file, &parser.data)?,
+    }
+    eprintln!("📊 {} nodes, {} ways", parser.data.nodes.len(), parser.data.ways.len());
+    Ok(())
+}
+
+// ---------------------------------------------------------------------
+// generate: OsmData JSON -> VrpInstance JSON (formerly osm_to_vrp_instance's main)
+// ---------------------------------------------------------------------
+
+fn generate_command() -> Command {
+    Command::new("generate")
+        .about("Creates a VRP instance from OSM road network data")
+        .arg(Arg::new("osm_json").long("osm-json").value_name("FILE")
+            .help("Path to the OSM data file ('-' or omitted reads JSON from stdin; a '.bin' \
+                   extension reads the compact bincode form instead)"))
+        .arg(Arg::new("output").short('o').long("output").value_name("FILE")
+            .help("Output VRP instance JSON file ('-' or omitted writes to stdout)"))
+        .args(generate_args())
+}
+
+/// The depot/customer/vehicle/demand arguments [`build_instance_from_osm`]
+/// needs, shared between `generate` and `solve`'s `--osm-json`/`--osm-pbf`
+/// inputs so the two subcommands don't drift out of sync with each other.
+fn generate_args() -> Vec<Arg> {
+    vec![
+        Arg::new("depot_lat").long("depot-lat").value_name("LATITUDE")
+            .help("Depot latitude coordinate").default_value("17.735"),
+        Arg::new("depot_lon").long("depot-lon").value_name("LONGITUDE")
+            .help("Depot longitude coordinate").default_value("83.315"),
+        Arg::new("num_customers").short('n').long("customers").value_name("COUNT")
+            .help("Number of customer locations").default_value("10"),
+        Arg::new("num_vehicles").short('v').long("vehicles").value_name("COUNT")
+            .help("Number of vehicles").default_value("3"),
+        Arg::new("vehicle_capacity").short('c').long("capacity").value_name("CAPACITY")
+            .help("Vehicle capacity units (comma-separated per dimension when --demand-dims is set)")
+            .default_value("100"),
+        Arg::new("min_demand").long("min-demand").value_name("DEMAND")
+            .help("Minimum customer demand (comma-separated per dimension when --demand-dims is set)")
+            .default_value("5"),
+        Arg::new("max_demand").long("max-demand").value_name("DEMAND")
+            .help("Maximum customer demand (comma-separated per dimension when --demand-dims is set)")
+            .default_value("25"),
+        Arg::new("demand_dims").long("demand-dims").value_name("NAMES")
+            .help("Comma-separated demand dimension names, e.g. \"weight,volume\", for multi-capacity CVRP instances"),
+        Arg::new("seed").long("seed").value_name("SEED")
+            .help("Random seed for reproducible instances").default_value("42"),
+        Arg::new("max_radius").long("max-radius").value_name("METERS")
+            .help("Maximum radius from depot to search for customers (meters)").default_value("1000"),
+        Arg::new("time_windows").long("time-windows")
+            .help("Generate VRPTW instances by assigning customers randomized time windows")
+            .action(clap::ArgAction::SetTrue),
+        Arg::new("horizon").long("horizon").value_name("SECONDS")
+            .help("Planning horizon customer time windows are drawn within").default_value("28800"),
+        Arg::new("window_width_min").long("window-width-min").value_name("SECONDS")
+            .help("Minimum width of a generated time window").default_value("1200"),
+        Arg::new("window_width_max").long("window-width-max").value_name("SECONDS")
+            .help("Maximum width of a generated time window").default_value("3600"),
+        Arg::new("window_density").long("window-density").value_name("FRACTION")
+            .help("Fraction of customers that get a time window, the rest stay unconstrained")
+            .default_value("0.5"),
+        Arg::new("mode").long("mode").value_name("MODE")
+            .help("Customer demand mode: pickup, delivery, mixed, or pickup-delivery").default_value("pickup"),
+        Arg::new("optional_fraction").long("optional-fraction").value_name("FRACTION")
+            .help("Fraction of customers (or pairs, in pickup-delivery mode) that become droppable")
+            .default_value("0.0"),
+        Arg::new("drop_penalty_range").long("drop-penalty-range").value_name("MIN,MAX")
+            .help("Range a droppable customer's skip penalty is sampled from").default_value("50,200"),
+        Arg::new("depot_name").long("depot-name").value_name("NAME")
+            .help("Resolve the depot by OSM place name (name/addr:* tags) instead of --depot-lat/--depot-lon"),
+        Arg::new("customer_names").long("customer-names").value_name("NAME1,NAME2,...")
+            .help("Resolve customers by OSM place name instead of randomly distributing --customers \
+                   within --max-radius"),
+    ]
+}
+
+/// Every parameter [`run_generate`] needs to turn loaded [`OsmData`] into a
+/// [`VrpInstance`]. Pulled out so [`run_solve`] can build an instance
+/// directly from OSM data too, via [`build_instance_from_osm`], instead of
+/// requiring a separate `generate` invocation first.
+struct GenerateParams {
+    depot_lat: f64,
+    depot_lon: f64,
+    num_customers: usize,
+    num_vehicles: usize,
+    seed: u64,
+    max_radius: f64,
+    time_windows: bool,
+    horizon: f64,
+    window_width_min: f64,
+    window_width_max: f64,
+    window_density: f64,
+    demand_dims: Vec<String>,
+    min_demand: Vec<f64>,
+    max_demand: Vec<f64>,
+    vehicle_capacity: Vec<f64>,
+    mode: String,
+    optional_fraction: f64,
+    drop_penalty_min: f64,
+    drop_penalty_max: f64,
+    depot_name: Option<String>,
+    customer_names: Vec<String>,
+}
+
+fn parse_generate_params(matches: &ArgMatches) -> Result<GenerateParams, Box<dyn std::error::Error>> {
+    let demand_dims: Vec<String> = matches.get_one::<String>("demand_dims")
+        .map(|s| s.split(',').map(|name| name.trim().to_string()).collect())
+        .unwrap_or_default();
+    let num_dims = demand_dims.len().max(1);
+
+    let min_demand = parse_per_dimension(matches.get_one::<String>("min_demand").unwrap(), num_dims)?;
+    let max_demand = parse_per_dimension(matches.get_one::<String>("max_demand").unwrap(), num_dims)?;
+    let vehicle_capacity = parse_per_dimension(matches.get_one::<String>("vehicle_capacity").unwrap(), num_dims)?;
+    let drop_penalty_range = parse_per_dimension(matches.get_one::<String>("drop_penalty_range").unwrap(), 2)?;
+
+    Ok(GenerateParams {
+        depot_lat: matches.get_one::<String>("depot_lat").unwrap().parse()?,
+        depot_lon: matches.get_one::<String>("depot_lon").unwrap().parse()?,
+        num_customers: matches.get_one::<String>("num_customers").unwrap().parse()?,
+        num_vehicles: matches.get_one::<String>("num_vehicles").unwrap().parse()?,
+        seed: matches.get_one::<String>("seed").unwrap().parse()?,
+        max_radius: matches.get_one::<String>("max_radius").unwrap().parse()?,
+        time_windows: matches.get_flag("time_windows"),
+        horizon: matches.get_one::<String>("horizon").unwrap().parse()?,
+        window_width_min: matches.get_one::<String>("window_width_min").unwrap().parse()?,
+        window_width_max: matches.get_one::<String>("window_width_max").unwrap().parse()?,
+        window_density: matches.get_one::<String>("window_density").unwrap().parse()?,
+        demand_dims,
+        min_demand,
+        max_demand,
+        vehicle_capacity,
+        mode: matches.get_one::<String>("mode").unwrap().clone(),
+        optional_fraction: matches.get_one::<String>("optional_fraction").unwrap().parse()?,
+        drop_penalty_min: drop_penalty_range[0],
+        drop_penalty_max: drop_penalty_range[1],
+        depot_name: matches.get_one::<String>("depot_name").cloned(),
+        customer_names: matches.get_one::<String>("customer_names")
+            .map(|s| s.split(',').map(|name| name.trim().to_string()).collect())
+            .unwrap_or_default(),
+    })
+}
+
+fn run_generate(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let osm_json_path = matches.get_one::<String>("osm_json");
+    let output_file = matches.get_one::<String>("output");
+    let params = parse_generate_params(matches)?;
+
+    let osm_data = read_osm_data(osm_json_path)?;
+    eprintln!("✅ Loaded {} road nodes, {} ways", osm_data.nodes.len(), osm_data.ways.len());
+
+    let instance = build_instance_from_osm(&osm_data, &params)?;
+
+    if let Some(path) = output_file.filter(|p| p.as_str() != "-") {
+        save_instance_to_json(&instance, path)?;
+        eprintln!("✅ Instance saved to: {}", path);
+    } else {
+        write_output(None, &instance)?;
+    }
+
+    Ok(())
+}
+
+/// Builds a [`VrpInstance`] from loaded OSM road network data: snaps the
+/// depot to the nearest node, distributes `params.num_customers` customers
+/// within `params.max_radius` of it, and assigns vehicles. Shared by
+/// `generate` and by `solve`'s `--osm-json`/`--osm-pbf` inputs so the two
+/// don't duplicate this logic.
+fn build_instance_from_osm(osm_data: &OsmData, params: &GenerateParams) -> Result<VrpInstance, Box<dyn std::error::Error>> {
+    let GenerateParams {
+        depot_lat, depot_lon, num_customers, num_vehicles, seed, max_radius,
+        time_windows, horizon, window_width_min, window_width_max, window_density,
+        demand_dims, min_demand, max_demand, vehicle_capacity, mode,
+        optional_fraction, drop_penalty_min, drop_penalty_max,
+        depot_name, customer_names,
+    } = params;
+    let (mut depot_lat, mut depot_lon, mut num_customers, num_vehicles, seed, max_radius) =
+        (*depot_lat, *depot_lon, *num_customers, *num_vehicles, *seed, *max_radius);
+    let (time_windows, horizon, window_width_min, window_width_max, window_density) =
+        (*time_windows, *horizon, *window_width_min, *window_width_max, *window_density);
+    let mode = mode.as_str();
+    let (optional_fraction, drop_penalty_min, drop_penalty_max) =
+        (*optional_fraction, *drop_penalty_min, *drop_penalty_max);
+    let num_dims = demand_dims.len().max(1);
+
+    // Built once whenever name-based resolution is requested, shared between
+    // the depot and the customer list so both go through the same index.
+    let name_index = if depot_name.is_some() || !customer_names.is_empty() {
+        Some(PlaceNameIndex::build(osm_data))
+    } else {
+        None
+    };
+
+    if let Some(name) = depot_name {
+        let place = name_index.as_ref().unwrap().resolve(name)?;
+        depot_lat = place.lat;
+        depot_lon = place.lon;
+    }
+
+    eprintln!("🏢 Depot: {:.4}°N, {:.4}°E", depot_lat, depot_lon);
+
+    // Indexed once and reused for depot snapping and the radius query below,
+    // rather than each doing its own O(n) scan over every node.
+    let osm_index = NodeSpatialIndex::build(osm_data);
+
+    let depot_node = osm_index.nearest(depot_lat, depot_lon);
+    let (depot_node_id, _depot_distance) = depot_node.ok_or("No depot node found in OSM data")?;
+    let depot_osm_node = osm_data.nodes.get(&depot_node_id).unwrap();
+
+    let selected_customers = if !customer_names.is_empty() {
+        let name_index = name_index.as_ref().unwrap();
+        let mut resolved = Vec::with_capacity(customer_names.len());
+        for name in customer_names {
+            let place = name_index.resolve(name)?;
+            let (node_id, _snap_distance) = osm_index.nearest(place.lat, place.lon)
+                .ok_or_else(|| format!("No routable node found near customer '{}'", name))?;
+            if node_id == depot_node_id {
+                return Err(format!("Customer '{}' resolves to the depot node", name).into());
+            }
+            let depot_distance = haversine_distance(depot_osm_node.lat, depot_osm_node.lon, place.lat, place.lon);
+            resolved.push((node_id, depot_distance));
+        }
+        num_customers = resolved.len();
+        resolved
+    } else {
+        eprintln!("👥 Customers: {} 🚛 Vehicles: {} (capacity: {:?})", num_customers, num_vehicles, vehicle_capacity);
+
+        let nearby_nodes = osm_index.within_radius(depot_osm_node.lat, depot_osm_node.lon, max_radius)
+            .into_iter()
+            .filter(|(node_id, _)| *node_id != depot_node_id)
+            .collect::<Vec<_>>();
+
+        if nearby_nodes.len() < num_customers {
+            return Err(format!(
+                "Not enough nodes within {}m radius. Found: {}, needed: {}",
+                max_radius, nearby_nodes.len(), num_customers
+            ).into());
+        }
+
+        select_distributed_customers(osm_data, &nearby_nodes, num_customers, seed)
+    };
+
+    if !customer_names.is_empty() {
+        eprintln!("👥 Customers: {} (resolved by name) 🚛 Vehicles: {} (capacity: {:?})", num_customers, num_vehicles, vehicle_capacity);
+    }
+
+    let average_speed_ms = 15.0; // ≈ 54 km/h
+    let mut builder = VrpInstanceBuilder::new()
+        .with_distance_method(DistanceMethod::Haversine)
+        .with_average_speed(average_speed_ms);
+
+    let depot_coord = Coordinate::new(depot_osm_node.lat, depot_osm_node.lon);
+    builder = builder.add_depot(depot_node_id as usize, "OSM Depot".to_string(), depot_coord);
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut windowed_count = 0usize;
+    let mut optional_count = 0usize;
+    let mut next_pair_id = 0usize;
+    let mut customer_num = 0usize;
+    let has_named_dims = !demand_dims.is_empty();
+
+    let mut idx = 0usize;
+    while idx < selected_customers.len() {
+        let pairing = mode == "pickup-delivery" && idx + 1 < selected_customers.len();
+
+        let magnitude_dims: Vec<f64> = (0..num_dims)
+            .map(|d| rng.gen_range(min_demand[d]..=max_demand[d]))
+            .collect();
+        let droppable = rng.gen::<f64>() < optional_fraction;
+        let drop_penalty = if droppable {
+            optional_count += 1;
+            Some(rng.gen_range(drop_penalty_min..=drop_penalty_max))
+        } else {
+            None
+        };
+        let pair_id = if pairing {
+            let id = next_pair_id;
+            next_pair_id += 1;
+            Some(id)
+        } else {
+            None
+        };
+
+        let first_sign = match mode {
+            "delivery" => -1.0,
+            "mixed" => if rng.gen_bool(0.5) { 1.0 } else { -1.0 },
+            _ => 1.0, // "pickup" and the pickup half of "pickup-delivery"
+        };
+
+        let (node_id, distance) = selected_customers[idx];
+        customer_num += 1;
+        let location = build_signed_customer(
+            node_id, distance, &magnitude_dims, first_sign, format!("OSM Customer {}", customer_num),
+            osm_data, average_speed_ms, time_windows, horizon, window_width_min, window_width_max, window_density,
+            pair_id, drop_penalty, has_named_dims, &mut rng, &mut windowed_count,
+        );
+        builder = builder.add_location(location);
+        idx += 1;
+
+        if pairing {
+            let (node_id, distance) = selected_customers[idx];
+            customer_num += 1;
+            let location = build_signed_customer(
+                node_id, distance, &magnitude_dims, -first_sign, format!("OSM Customer {}", customer_num),
+                osm_data, average_speed_ms, time_windows, horizon, window_width_min, window_width_max, window_density,
+                pair_id, drop_penalty, has_named_dims, &mut rng, &mut windowed_count,
+            );
+            builder = builder.add_location(location);
+            idx += 1;
+        }
+    }
+
+    for i in 0..num_vehicles {
+        builder = if demand_dims.is_empty() {
+            builder.add_vehicle_simple(i, vehicle_capacity[0], depot_node_id as usize)
+        } else {
+            builder.add_vehicle_multi_dim(i, vehicle_capacity.clone(), depot_node_id as usize)
+        };
+    }
+
+    let instance = builder.build()?;
+    eprintln!("✅ Created VRP instance: {} locations, {} vehicles", instance.locations.len(), instance.vehicles.len());
+    if time_windows {
+        eprintln!("   {} of {} customers given a time window", windowed_count, num_customers);
+    }
+    if mode == "pickup-delivery" {
+        eprintln!("   {} linked pickup/delivery pairs", next_pair_id);
+    }
+    if optional_fraction > 0.0 {
+        eprintln!("   {} stops marked droppable", optional_count);
+    }
+
+    for d in 0..num_dims {
+        let dim_name = demand_dims.get(d).cloned().unwrap_or_else(|| "demand".to_string());
+        let total_demand: f64 = instance.locations.iter()
+            .filter_map(|loc| loc.demand_dims.as_ref().map(|dims| dims[d]).or(if d == 0 { Some(loc.demand) } else { None }))
+            .filter(|&v| v > 0.0)
+            .sum();
+        let total_capacity = num_vehicles as f64 * vehicle_capacity[d];
+        let capacity_utilization = (total_demand / total_capacity) * 100.0;
+        eprintln!("   [{}] demand: {:.1}, fleet capacity: {:.1}, utilization: {:.1}%",
+                 dim_name, total_demand, total_capacity, capacity_utilization);
+        if capacity_utilization > 95.0 {
+            eprintln!("   ⚠️  [{}] capacity utilization is high. Consider adding more vehicles.", dim_name);
+        }
+    }
+
+    Ok(instance)
+}
+
+/// Builds one customer stop: resolves its OSM coordinate, rolls a service
+/// time and (if enabled) a feasible time window, then signs `magnitude_dims`
+/// by `sign` (positive to load, negative to unload) before handing off to
+/// [`build_customer_location`] for pairing/drop-penalty attachment.
+#[allow(clippy::too_many_arguments)]
+fn build_signed_customer(
+    node_id: i64,
+    distance: f64,
+    magnitude_dims: &[f64],
+    sign: f64,
+    name: String,
+    osm_data: &OsmData,
+    average_speed_ms: f64,
+    time_windows: bool,
+    horizon: f64,
+    window_width_min: f64,
+    window_width_max: f64,
+    window_density: f64,
+    pair_id: Option<usize>,
+    drop_penalty: Option<f64>,
+    has_named_dims: bool,
+    rng: &mut StdRng,
+    windowed_count: &mut usize,
+) -> Location {
+    let node = osm_data.nodes.get(&node_id).unwrap();
+    let coord = Coordinate::new(node.lat, node.lon);
+    let service_time = rng.gen_range(300.0..=900.0); // 5-15 minutes
+
+    let time_window = if time_windows && rng.gen::<f64>() < window_density {
+        let earliest_arrival = distance / average_speed_ms;
+        let width = rng.gen_range(window_width_min..=window_width_max);
+        let slack_budget = (horizon - earliest_arrival - width).max(0.0);
+        let slack = rng.gen_range(0.0..=slack_budget);
+        let start = earliest_arrival + slack;
+        *windowed_count += 1;
+        Some(TimeWindow::new(start, (start + width).min(horizon)))
+    } else {
+        None
+    };
+
+    let dims: Vec<f64> = magnitude_dims.iter().map(|v| v * sign).collect();
+    build_customer_location(node_id as usize, name, coord, dims, time_window, service_time, pair_id, drop_penalty, has_named_dims)
+}
+
+/// Assembles a [`Location`] from generated attributes, using the scalar
+/// `demand` field unless `--demand-dims` named multiple dimensions.
+#[allow(clippy::too_many_arguments)]
+fn build_customer_location(
+    id: usize,
+    name: String,
+    coordinate: Coordinate,
+    demand_dims: Vec<f64>,
+    time_window: Option<TimeWindow>,
+    service_time: f64,
+    pair_id: Option<usize>,
+    drop_penalty: Option<f64>,
+    has_named_dims: bool,
+) -> Location {
+    let mut location = if has_named_dims {
+        Location::new(id, name, coordinate, 0.0, time_window, service_time).with_demand_dims(demand_dims)
+    } else {
+        let demand = demand_dims.first().copied().unwrap_or(0.0);
+        Location::new(id, name, coordinate, demand, time_window, service_time)
+    };
+
+    if let Some(pair_id) = pair_id {
+        location = location.with_pickup_pair_id(pair_id);
+    }
+    if let Some(penalty) = drop_penalty {
+        location = location.with_drop_penalty(penalty);
+    }
+    location
+}
+
+/// Parses a comma-separated list of `f64`s for a per-dimension CLI argument
+/// (e.g. `--capacity 100,50`). A single value is broadcast across all
+/// `num_dims` dimensions; otherwise the list must have exactly `num_dims` entries.
+fn parse_per_dimension(raw: &str, num_dims: usize) -> Result<Vec<f64>, Box<dyn std::error::Error>> {
+    let values: Vec<f64> = raw.split(',')
+        .map(|part| part.trim().parse::<f64>())
+        .collect::<Result<_, _>>()?;
+
+    if values.len() == 1 {
+        Ok(vec![values[0]; num_dims])
+    } else if values.len() == num_dims {
+        Ok(values)
+    } else {
+        Err(format!("expected 1 or {} comma-separated values, got {}", num_dims, values.len()).into())
+    }
+}
+
+/// Greedy k-center (farthest-first traversal) over real haversine distances,
+/// so customers generated from OSM data are genuinely spread out rather than
+/// clustered by coincidence of node ID. `candidates` is assumed sorted by
+/// distance from the depot (as returned by `NodeSpatialIndex::within_radius`),
+/// so its first entry seeds the selection.
+///
+/// Each remaining candidate's distance to the nearest already-selected point
+/// is tracked incrementally (`min_dist`), updated only against the point
+/// just added rather than rescanned against the whole `selected` set each
+/// round — O(count · remaining) instead of O(count · remaining · selected).
+fn select_distributed_customers(osm_data: &OsmData, candidates: &[(i64, f64)], count: usize, seed: u64) -> Vec<(i64, f64)> {
+    if candidates.len() <= count {
+        return candidates.to_vec();
+    }
+
+    let mut selected = Vec::new();
+    let mut remaining = candidates.to_vec();
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    selected.push(remaining.remove(0));
+
+    let seed_node = osm_data.nodes.get(&selected[0].0).unwrap();
+    let mut min_dist: Vec<f64> = remaining.iter()
+        .map(|(id, _)| {
+            let node = osm_data.nodes.get(id).unwrap();
+            haversine_distance(seed_node.lat, seed_node.lon, node.lat, node.lon)
+        })
+        .collect();
+
+    while selected.len() < count && !remaining.is_empty() {
+        let mut ranked: Vec<(usize, f64)> = min_dist.iter().copied().enumerate().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        // Add some randomness to avoid too regular patterns: usually take the
+        // farthest candidate, but occasionally pick from the top 5 instead.
+        let top_n = ranked.len().min(5);
+        let selection_idx = if rng.gen::<f64>() < 0.7 {
+            ranked[0].0
+        } else {
+            ranked[rng.gen_range(0..top_n)].0
+        };
+
+        let picked = remaining.remove(selection_idx);
+        min_dist.remove(selection_idx);
+
+        let picked_node = osm_data.nodes.get(&picked.0).unwrap();
+        for (i, (id, _)) in remaining.iter().enumerate() {
+            let node = osm_data.nodes.get(id).unwrap();
+            let d = haversine_distance(picked_node.lat, picked_node.lon, node.lat, node.lon);
+            if d < min_dist[i] {
+                min_dist[i] = d;
+            }
+        }
+
+        selected.push(picked);
+    }
+
+    selected
+}
+
+fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let r = 6371000.0; // Earth's radius in meters
+    let phi1 = lat1.to_radians();
+    let phi2 = lat2.to_radians();
+    let delta_phi = (lat2 - lat1).to_radians();
+    let delta_lambda = (lon2 - lon1).to_radians();
+
+    let a = (delta_phi / 2.0).sin().powi(2) +
+            phi1.cos() * phi2.cos() *
+            (delta_lambda / 2.0).sin().powi(2);
+    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+
+    r * c
+}
+
+// ---------------------------------------------------------------------
+// solve: VrpInstance JSON -> Solution JSON
+// ---------------------------------------------------------------------
+
+fn solve_command() -> Command {
+    Command::new("solve")
+        .about("Builds (if needed) and solves a VRP instance")
+        .arg(Arg::new("instance").long("instance").value_name("FILE")
+            .help("Pre-built VRP instance JSON ('-' or omitted reads stdin when neither \
+                   --osm-json nor --osm-pbf is given)"))
+        .arg(Arg::new("osm_json").long("osm-json").value_name("FILE")
+            .help("Already-converted OSM data to build the instance from, instead of --instance \
+                   ('-' or omitted reads JSON from stdin; a '.bin' extension reads the compact \
+                   bincode form instead)")
+            .conflicts_with("instance"))
+        .arg(Arg::new("osm_pbf").long("osm-pbf").value_name("FILE")
+            .help("Raw OSM PBF file to convert and build the instance from inline, instead of \
+                   --instance or --osm-json")
+            .conflicts_with_all(["instance", "osm_json"]))
+        .arg(Arg::new("postgis").long("postgis").value_name("URL")
+            .help("Postgres connection URL to load the road network from PostGIS instead of \
+                   --instance, --osm-json or --osm-pbf")
+            .conflicts_with_all(["instance", "osm_json", "osm_pbf"]))
+        .arg(Arg::new("postgis_node_table").long("postgis-node-table").value_name("TABLE")
+            .help("PostGIS vertex table (id + point geometry)").default_value("nodes"))
+        .arg(Arg::new("postgis_node_geom").long("postgis-node-geom").value_name("COLUMN")
+            .help("PostGIS vertex table's geometry column").default_value("geom"))
+        .arg(Arg::new("postgis_edge_table").long("postgis-edge-table").value_name("TABLE")
+            .help("PostGIS edge table (from/to vertex ids + cost)").default_value("edges"))
+        .arg(Arg::new("postgis_edge_geom").long("postgis-edge-geom").value_name("COLUMN")
+            .help("PostGIS edge table's geometry column").default_value("geom"))
+        .arg(Arg::new("postgis_from_column").long("postgis-from-column").value_name("COLUMN")
+            .help("PostGIS edge table's source vertex id column").default_value("source"))
+        .arg(Arg::new("postgis_to_column").long("postgis-to-column").value_name("COLUMN")
+            .help("PostGIS edge table's target vertex id column").default_value("target"))
+        .arg(Arg::new("postgis_cost_column").long("postgis-cost-column").value_name("COLUMN")
+            .help("PostGIS edge table's precomputed edge cost column").default_value("cost"))
+        .args(generate_args())
+        .arg(Arg::new("output").short('o').long("output").value_name("FILE")
+            .help("Output solution JSON file ('-' or omitted writes to stdout)"))
+        .arg(Arg::new("algorithm").long("algorithm").value_name("NAME")
+            .help("greedy, greedy_farthest, clarke_wright, or multi_start").default_value("multi_start"))
+        .arg(Arg::new("distance_method").long("distance-method").value_name("METHOD")
+            .help("haversine, euclidean, or manhattan (only used when the instance has no distance matrix yet)")
+            .default_value("haversine"))
+        .arg(Arg::new("parallel").long("parallel")
+            .help("Run clarke_wright's savings merge in parallel").action(clap::ArgAction::SetTrue))
+}
+
+fn run_solve(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let instance_path = matches.get_one::<String>("instance");
+    let osm_json_path = matches.get_one::<String>("osm_json");
+    let osm_pbf_path = matches.get_one::<String>("osm_pbf");
+    let postgis_url = matches.get_one::<String>("postgis");
+    let output_file = matches.get_one::<String>("output");
+    let algorithm = matches.get_one::<String>("algorithm").unwrap().as_str();
+    let distance_method = matches.get_one::<String>("distance_method").unwrap().as_str();
+    let parallel = matches.get_flag("parallel");
+
+    let mut instance: VrpInstance = if let Some(pbf_path) = osm_pbf_path {
+        let mut parser = OsmParser::new();
+        parser.parse_pbf_file(pbf_path)?;
+        eprintln!("✅ Converted {} road nodes, {} ways from {}", parser.data.nodes.len(), parser.data.ways.len(), pbf_path);
+        build_instance_from_osm(&parser.data, &parse_generate_params(matches)?)?
+    } else if osm_json_path.is_some() {
+        let osm_data = read_osm_data(osm_json_path)?;
+        eprintln!("✅ Loaded {} road nodes, {} ways", osm_data.nodes.len(), osm_data.ways.len());
+        build_instance_from_osm(&osm_data, &parse_generate_params(matches)?)?
+    } else if let Some(url) = postgis_url {
+        let source = PostgisSource {
+            connection_url: url.clone(),
+            node_table: matches.get_one::<String>("postgis_node_table").unwrap().clone(),
+            node_geom_column: matches.get_one::<String>("postgis_node_geom").unwrap().clone(),
+            edge_table: matches.get_one::<String>("postgis_edge_table").unwrap().clone(),
+            geometry_column: matches.get_one::<String>("postgis_edge_geom").unwrap().clone(),
+            from_node_column: matches.get_one::<String>("postgis_from_column").unwrap().clone(),
+            to_node_column: matches.get_one::<String>("postgis_to_column").unwrap().clone(),
+            cost_column: matches.get_one::<String>("postgis_cost_column").unwrap().clone(),
+        };
+        let runtime = tokio::runtime::Runtime::new()?;
+        let osm_data = runtime.block_on(source.load())?;
+        eprintln!("✅ Loaded {} road nodes, {} edges from PostGIS", osm_data.nodes.len(), osm_data.ways.len());
+        build_instance_from_osm(&osm_data, &parse_generate_params(matches)?)?
+    } else {
+        serde_json::from_str(&read_input(instance_path)?)?
+    };
+
+    // An empty distance matrix (e.g. a freshly-generated instance that
+    // skipped `calculate_distance_matrix`) is recomputed; a precomputed one
+    // (from `generate`/OSM input, or piped in from road-network timing) is kept as-is.
+    if instance.distance_matrix.iter().all(|row| row.iter().all(|&d| d == 0.0)) {
+        let method = match distance_method {
+            "euclidean" => DistanceMethod::Euclidean,
+            "manhattan" => DistanceMethod::Manhattan,
+            _ => DistanceMethod::Haversine,
+        };
+        calculate_distance_matrix(&mut instance, method);
+    }
+
+    let solver: Box<dyn VrpSolver + Sync> = match algorithm {
+        "greedy" => Box::new(GreedyNearestNeighbor::new()),
+        "greedy_farthest" => Box::new(GreedyNearestNeighbor::new().with_farthest_start(true)),
+        "clarke_wright" => Box::new(ClarkeWrightSavings::new().with_parallel(parallel)),
+        _ => Box::new(MultiStartSolver::new().with_default_solvers()),
+    };
+
+    let start_time = std::time::Instant::now();
+    let solution: Solution = solver.solve(&instance).map_err(|e| e.to_string())?;
+    let solve_time_ms = start_time.elapsed().as_millis();
+
+    eprintln!("✅ Solved with {} in {}ms: {} routes, total distance {:.1}",
+             solver.name(), solve_time_ms, solution.routes.len(), solution.total_distance);
+
+    write_output(output_file, &solution)
+}
+
+// ---------------------------------------------------------------------
+// export: VrpInstance + Solution JSON -> GeoJSON
+// ---------------------------------------------------------------------
+
+fn export_command() -> Command {
+    Command::new("export")
+        .about("Renders a solved solution as a GeoJSON FeatureCollection")
+        .arg(Arg::new("instance").long("instance").value_name("FILE")
+            .help("VRP instance JSON the solution was solved against").required(true))
+        .arg(Arg::new("solution").long("solution").value_name("FILE")
+            .help("Solution JSON ('-' or omitted reads stdin)"))
+        .arg(Arg::new("output").short('o').long("output").value_name("FILE")
+            .help("Output GeoJSON file ('-' or omitted writes to stdout)"))
+        .arg(Arg::new("road_geometry").long("road-geometry").value_name("FILE")
+            .help("Optional JSON file of per-route [lon,lat] polylines (e.g. from routing::RoadGraph) to follow instead of straight depot-stop-depot lines"))
+}
+
+fn run_export(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
+    let instance_path = matches.get_one::<String>("instance").unwrap();
+    let solution_path = matches.get_one::<String>("solution");
+    let output_file = matches.get_one::<String>("output");
+    let road_geometry_path = matches.get_one::<String>("road_geometry");
+
+    let instance: VrpInstance = serde_json::from_reader(BufReader::new(File::open(instance_path)?))?;
+    let solution: Solution = serde_json::from_str(&read_input(solution_path)?)?;
+
+    let geojson = if let Some(path) = road_geometry_path {
+        let road_geometry: Vec<Vec<[f64; 2]>> = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        solution_to_geojson_with_road_geometry(&solution, &instance, &road_geometry)
+    } else {
+        solution_to_geojson(&solution, &instance)
+    };
+
+    write_output(output_file, &geojson)
+}
@@ -1,15 +1,18 @@
 use clap::{Arg, Command};
 use serde_json::Map;
-use std::fs::File;
-use std::io::BufReader;
 use vrp_solver::{
     distance::DistanceMethod,
+    routing::{RoadGraph, build_road_network_matrix},
     solver::{GreedyNearestNeighbor, MultiStartSolver, VrpSolver},
     types::{Coordinate, Solution},
     utils::{VrpInstanceBuilder, save_solution_to_json},
-    osm_parser::OsmData,
+    osm_parser::{load_osm_data, OsmData, NodeSpatialIndex},
+    osrm::{encode_polyline, solution_to_osrm_export_with_road_geometry},
+    validate::{RouteValidator, RouteActivities, ActivityKind},
+    VrpResult,
 };
 use geojson::{GeoJson, Geometry, Value, Feature, FeatureCollection};
+use serde::Serialize;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let matches = Command::new("Planet 83 OSM VRP Example")
@@ -46,12 +49,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .help("Depot longitude")
                 .default_value("83.315"),
         )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .help("Route export format")
+                .value_parser(["geojson", "osrm", "polyline"])
+                .default_value("geojson"),
+        )
         .get_matches();
 
     let osm_data_file = matches.get_one::<String>("osm_data").unwrap();
     let num_customers: usize = matches.get_one::<String>("num_customers").unwrap().parse()?;
     let depot_lat: f64 = matches.get_one::<String>("depot_lat").unwrap().parse()?;
     let depot_lon: f64 = matches.get_one::<String>("depot_lon").unwrap().parse()?;
+    let format = matches.get_one::<String>("format").unwrap().as_str();
 
     println!("🌍 Planet 83 OSM VRP Workflow Example");
     println!("=====================================");
@@ -67,25 +79,33 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    let osm_file_handle = File::open(osm_data_file)?;
-    let osm_reader = BufReader::new(osm_file_handle);
-    let osm_data: OsmData = serde_json::from_reader(osm_reader)?;
-    
+    // Transparently accepts either JSON or the compact `.bin` (bincode) form
+    // `osm_converter --bin`/`optiqroute convert -o *.bin` can emit, which
+    // skips JSON's text parse entirely on large bounding boxes.
+    let osm_data: OsmData = load_osm_data(osm_data_file)?;
+
     println!("✅ Loaded OSM data: {} nodes, {} ways", osm_data.nodes.len(), osm_data.ways.len());
 
     // Step 2: Find depot node and nearby customer locations
     println!("\n🎯 Step 2: Finding depot and customer locations...");
-    
+
+    // Bulk-load every node into an R-tree once, so depot + customer lookups
+    // don't each re-scan the whole node set (this extract can have hundreds
+    // of thousands of nodes).
+    let spatial_index = NodeSpatialIndex::build(&osm_data);
+
     // Find nearest OSM node to depot coordinates
-    let depot_node = find_nearest_node(&osm_data, depot_lat, depot_lon);
+    let depot_node = spatial_index.nearest(depot_lat, depot_lon);
     let (depot_node_id, depot_distance) = depot_node.ok_or("No depot node found")?;
     let depot_osm_node = osm_data.nodes.get(&depot_node_id).unwrap();
-    
+
     println!("🏢 Depot mapped to OSM node {} ({:.2}m away)", depot_node_id, depot_distance);
     println!("   Coordinates: {:.6}, {:.6}", depot_osm_node.lat, depot_osm_node.lon);
 
-    // Find customer locations (nearby OSM nodes)
-    let customer_nodes = find_nearby_nodes(&osm_data, depot_osm_node.lat, depot_osm_node.lon, num_customers + 5)
+    // Find customer locations (nearby OSM nodes), with a little slack so
+    // excluding the depot node still leaves enough candidates.
+    let customer_nodes = spatial_index
+        .k_nearest(depot_osm_node.lat, depot_osm_node.lon, num_customers + 5)
         .into_iter()
         .filter(|(node_id, _)| *node_id != depot_node_id)  // Exclude depot
         .take(num_customers)
@@ -142,10 +162,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         builder = builder.add_vehicle_simple(i, 100.0, depot_node_id as usize);
     }
 
-    let instance = builder.build()?;
-    println!("✅ Created VRP instance with {} locations, {} vehicles", 
+    let mut instance = builder.build()?;
+    println!("✅ Created VRP instance with {} locations, {} vehicles",
              instance.locations.len(), instance.vehicles.len());
 
+    // Replace the haversine cost matrix with real road-network distances
+    // (and, since an average speed is on hand, travel times), routed over
+    // the ways just parsed from OSM.
+    println!("\n🛣️  Step 3b: Routing over the road network...");
+    let road_graph = RoadGraph::build(&osm_data);
+    let road_matrix = build_road_network_matrix(&mut instance, &osm_data, &road_graph, Some(15.0))?;
+    if road_matrix.disconnected_locations.is_empty() {
+        println!("✅ All locations connected via the road network");
+    } else {
+        println!(
+            "⚠️  {} location(s) disconnected from the road network; fell back to haversine for those pairs",
+            road_matrix.disconnected_locations.len()
+        );
+    }
+
     // Step 4: Solve VRP
     println!("\n🧮 Step 4: Solving VRP...");
     
@@ -165,11 +200,48 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     save_solution_to_json(&solution, solution_file)?;
     println!("✅ Solution saved to: {}", solution_file);
 
-    // Step 6: Export to GeoJSON
-    println!("\n🌍 Step 6: Exporting to GeoJSON...");
-    let geojson_file = "planet_83_routes.geojson";
-    export_solution_to_geojson(&solution, &instance, geojson_file, Some(depot_coord))?;
-    println!("✅ GeoJSON exported to: {}", geojson_file);
+    // Step 5b: Compute a pragmatic-style per-stop activity timeline (arrival/
+    // departure time and running vehicle load) for each route, and save it
+    // as its own schedule file alongside the raw solution.
+    println!("\n⏱️  Step 5b: Computing route activity timelines...");
+    let validator = RouteValidator::new();
+    let activities: Vec<RouteActivities> = solution.routes
+        .iter()
+        .map(|route| validator.compute_activities(&instance, route))
+        .collect::<VrpResult<Vec<_>>>()?;
+    let activities_file = "planet_83_activities.json";
+    std::fs::write(activities_file, serde_json::to_string_pretty(&activities)?)?;
+    println!("✅ Activity timelines saved to: {}", activities_file);
+
+    // Step 6: Export routes in the requested format
+    println!("\n🌍 Step 6: Exporting routes ({})...", format);
+    let route_file = match format {
+        "osrm" => {
+            let file = "planet_83_routes.osrm.json";
+            let road_geometry: Vec<Vec<[f64; 2]>> = solution.routes
+                .iter()
+                .map(|route| route_road_coordinates(route, &instance, &osm_data, &road_graph).0)
+                .collect();
+            let osrm_response = solution_to_osrm_export_with_road_geometry(&solution, &instance, Some(&road_geometry), 5);
+            std::fs::write(file, serde_json::to_string_pretty(&osrm_response)?)?;
+            file
+        }
+        "polyline" => {
+            let file = "planet_83_routes.polyline.json";
+            let routes: Vec<PolylineRoute> = solution.routes
+                .iter()
+                .map(|route| route_to_polyline(route, &instance, &osm_data, &road_graph))
+                .collect();
+            std::fs::write(file, serde_json::to_string_pretty(&routes)?)?;
+            file
+        }
+        _ => {
+            let file = "planet_83_routes.geojson";
+            export_solution_to_geojson(&solution, &instance, &osm_data, &road_graph, &activities, file, Some(depot_coord))?;
+            file
+        }
+    };
+    println!("✅ Routes exported to: {}", route_file);
 
     println!("\n🎉 Complete workflow finished successfully!");
     println!("📊 Summary:");
@@ -177,64 +249,118 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   - Created VRP with {} locations using real coordinates", instance.locations.len());
     println!("   - Solved with {} routes, {:.2}km total distance", 
              solution.routes.len(), solution.total_distance / 1000.0);
-    println!("   - Exported solution and GeoJSON for visualization");
+    println!("   - Exported solution and routes ({}) for visualization", format);
     println!("\n📂 Files created:");
     println!("   - {}: VRP solution data", solution_file);
-    println!("   - {}: GeoJSON for map visualization", geojson_file);
+    println!("   - {}: route export ({})", route_file, format);
     println!("\n💡 Next steps:");
-    println!("   - Open {} in QGIS, Leaflet, or any GIS application", geojson_file);
+    println!("   - Open {} in QGIS, Leaflet, or any GIS application (for geojson)", route_file);
     println!("   - Use the solution data for further analysis or visualization");
 
     Ok(())
 }
 
-fn find_nearest_node(osm_data: &OsmData, target_lat: f64, target_lon: f64) -> Option<(i64, f64)> {
-    let mut nearest_node = None;
-    let mut min_distance = f64::MAX;
+/// Walks `route`'s depot-stop-depot hops, following `road_graph`'s shortest
+/// path between each consecutive pair instead of a straight line, falling
+/// back to a direct segment for any hop the road network can't bridge.
+/// Returns the stitched `[lon, lat]` coordinate sequence plus whether any
+/// hop needed that straight-line fallback.
+fn route_road_coordinates(
+    route: &vrp_solver::types::Route,
+    instance: &vrp_solver::types::VrpInstance,
+    osm_data: &OsmData,
+    road_graph: &RoadGraph,
+) -> (Vec<[f64; 2]>, bool) {
+    let mut node_ids: Vec<i64> = Vec::with_capacity(route.locations.len() + 2);
+    node_ids.extend(instance.get_vehicle(route.vehicle_id).map(|v| v.depot_id as i64));
+    node_ids.extend(route.locations.iter().map(|&id| id as i64));
+    node_ids.extend(instance.get_vehicle(route.vehicle_id).map(|v| v.depot_id as i64));
+
+    let mut coordinates: Vec<[f64; 2]> = Vec::new();
+    let mut disconnected = false;
+
+    for pair in node_ids.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        let segment: Vec<[f64; 2]> = match road_graph.shortest_path(from, to) {
+            Some(path) => path
+                .iter()
+                .filter_map(|node_id| osm_data.nodes.get(node_id))
+                .map(|node| [node.lon, node.lat])
+                .collect(),
+            None => {
+                disconnected = true;
+                let (Some(from_loc), Some(to_loc)) = (instance.get_location(from as usize), instance.get_location(to as usize)) else {
+                    continue;
+                };
+                vec![
+                    [from_loc.coordinate.lon, from_loc.coordinate.lat],
+                    [to_loc.coordinate.lon, to_loc.coordinate.lat],
+                ]
+            }
+        };
 
-    for (&node_id, node) in &osm_data.nodes {
-        let distance = haversine_distance(target_lat, target_lon, node.lat, node.lon);
-        if distance < min_distance {
-            min_distance = distance;
-            nearest_node = Some((node_id, distance));
-        }
+        let start = if coordinates.is_empty() { 0 } else { 1 };
+        coordinates.extend(segment.into_iter().skip(start));
     }
 
-    nearest_node
+    (coordinates, disconnected)
 }
 
-fn find_nearby_nodes(osm_data: &OsmData, center_lat: f64, center_lon: f64, count: usize) -> Vec<(i64, f64)> {
-    let mut nodes_with_distances: Vec<(i64, f64)> = osm_data.nodes
-        .iter()
-        .map(|(&node_id, node)| {
-            let distance = haversine_distance(center_lat, center_lon, node.lat, node.lon);
-            (node_id, distance)
-        })
-        .collect();
+/// One route exported as a Google/OSRM encoded polyline, with per-leg
+/// distance/duration so clients don't need to recompute them from the
+/// decoded geometry.
+#[derive(Debug, Serialize)]
+struct PolylineRoute {
+    vehicle_id: usize,
+    distance: f64,
+    duration: f64,
+    polyline: String,
+    legs: Vec<PolylineLeg>,
+}
 
-    // Sort by distance and take the closest nodes
-    nodes_with_distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-    nodes_with_distances.into_iter().take(count).collect()
+#[derive(Debug, Serialize)]
+struct PolylineLeg {
+    distance: f64,
+    duration: f64,
 }
 
-fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
-    let r = 6371000.0; // Earth's radius in meters
-    let phi1 = lat1.to_radians();
-    let phi2 = lat2.to_radians();
-    let delta_phi = (lat2 - lat1).to_radians();
-    let delta_lambda = (lon2 - lon1).to_radians();
+fn route_to_polyline(
+    route: &vrp_solver::types::Route,
+    instance: &vrp_solver::types::VrpInstance,
+    osm_data: &OsmData,
+    road_graph: &RoadGraph,
+) -> PolylineRoute {
+    let depot_id = instance.get_vehicle(route.vehicle_id).map(|v| v.depot_id);
+    let mut stop_ids = Vec::with_capacity(route.locations.len() + 2);
+    stop_ids.extend(depot_id);
+    stop_ids.extend(route.locations.iter().copied());
+    stop_ids.extend(depot_id);
+
+    let legs = stop_ids
+        .windows(2)
+        .map(|pair| PolylineLeg {
+            distance: instance.get_distance(pair[0], pair[1]),
+            duration: instance.time_matrix.as_ref().map(|m| m[pair[0]][pair[1]]).unwrap_or(0.0),
+        })
+        .collect();
 
-    let a = (delta_phi / 2.0).sin().powi(2) +
-            phi1.cos() * phi2.cos() *
-            (delta_lambda / 2.0).sin().powi(2);
-    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+    let (coordinates, _disconnected) = route_road_coordinates(route, instance, osm_data, road_graph);
 
-    r * c
+    PolylineRoute {
+        vehicle_id: route.vehicle_id,
+        distance: route.total_distance,
+        duration: route.total_duration,
+        polyline: encode_polyline(&coordinates, 5),
+        legs,
+    }
 }
 
 fn export_solution_to_geojson(
     solution: &Solution,
     instance: &vrp_solver::types::VrpInstance,
+    osm_data: &OsmData,
+    road_graph: &RoadGraph,
+    activities: &[RouteActivities],
     file_path: &str,
     depot_coords: Option<Coordinate>,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -242,19 +368,14 @@ fn export_solution_to_geojson(
 
     // Process each route
     for (route_idx, route) in solution.routes.iter().enumerate() {
-        let mut coordinates = Vec::new();
-
-        // Add depot at the beginning if provided
-        if let Some(depot) = depot_coords {
-            coordinates.push(vec![depot.lon, depot.lat]); // GeoJSON uses [lon, lat]
-        }
+        let (road_coords, disconnected) = route_road_coordinates(route, instance, osm_data, road_graph);
+        let coordinates: Vec<Vec<f64>> = road_coords.into_iter().map(|c| vec![c[0], c[1]]).collect();
 
-        // Get coordinates for each location in the route
+        // Add individual point features for each customer stop, annotated
+        // with this stop's computed activity timing/load where available.
+        let route_activities = activities.get(route_idx);
         for &location_id in &route.locations {
             if let Some(location) = instance.get_location(location_id) {
-                coordinates.push(vec![location.coordinate.lon, location.coordinate.lat]);
-
-                // Add individual point feature for customer
                 let mut properties = Map::new();
                 properties.insert("type".to_string(), serde_json::Value::String("customer".to_string()));
                 properties.insert("location_id".to_string(), serde_json::Value::Number(location_id.into()));
@@ -263,6 +384,13 @@ fn export_solution_to_geojson(
                 properties.insert("vehicle_id".to_string(), serde_json::Value::Number(route.vehicle_id.into()));
                 properties.insert("demand".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(location.demand).unwrap()));
 
+                if let Some(activity) = route_activities.and_then(|ra| {
+                    ra.activities.iter().find(|a| a.kind == ActivityKind::Service && a.location_id == location_id)
+                }) {
+                    properties.insert("arrival_time".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(activity.arrival).unwrap()));
+                    properties.insert("load_after".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(activity.load).unwrap()));
+                }
+
                 let point_feature = Feature {
                     bbox: None,
                     geometry: Some(Geometry::new(Value::Point(vec![location.coordinate.lon, location.coordinate.lat]))),
@@ -275,11 +403,6 @@ fn export_solution_to_geojson(
             }
         }
 
-        // Add depot at the end (complete the route)
-        if let Some(depot) = depot_coords {
-            coordinates.push(vec![depot.lon, depot.lat]);
-        }
-
         // Create LineString feature for the route
         if coordinates.len() >= 2 {
             let mut properties = Map::new();
@@ -288,6 +411,7 @@ fn export_solution_to_geojson(
             properties.insert("vehicle_id".to_string(), serde_json::Value::Number(route.vehicle_id.into()));
             properties.insert("total_distance".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(route.total_distance).unwrap()));
             properties.insert("num_locations".to_string(), serde_json::Value::Number(route.locations.len().into()));
+            properties.insert("disconnected".to_string(), serde_json::Value::Bool(disconnected));
 
             let route_feature = Feature {
                 bbox: None,
@@ -0,0 +1,124 @@
+//! Diffing two solutions for the same VRP instance, for re-optimization
+//! workflows where an operator needs to see what changed between runs
+//! instead of the full solution again.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::Solution;
+
+/// What changed between an `old` and `new` solution for the same instance.
+/// Routes are matched by `vehicle_id`; customers are matched by their
+/// assignment (customer -> vehicle) in each solution, set-differenced to
+/// find who moved. Customers present in only one solution (e.g. a dropped
+/// stop) have no counterpart and so aren't reported as moved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolutionDiff {
+    pub routes_added: Vec<usize>,
+    pub routes_removed: Vec<usize>,
+    /// `(customer_id, from_vehicle, to_vehicle)` for every customer served by
+    /// a different vehicle in `new` than in `old`.
+    pub customers_moved: Vec<(usize, usize, usize)>,
+    pub distance_delta: f64,
+    pub duration_delta: f64,
+    pub vehicles_delta: i64,
+}
+
+/// Computes the [`SolutionDiff`] moving from `old` to `new`.
+pub fn diff_solutions(old: &Solution, new: &Solution) -> SolutionDiff {
+    let old_vehicles: HashSet<usize> = old.routes.iter().map(|r| r.vehicle_id).collect();
+    let new_vehicles: HashSet<usize> = new.routes.iter().map(|r| r.vehicle_id).collect();
+
+    let mut routes_added: Vec<usize> = new_vehicles.difference(&old_vehicles).copied().collect();
+    routes_added.sort_unstable();
+    let mut routes_removed: Vec<usize> = old_vehicles.difference(&new_vehicles).copied().collect();
+    routes_removed.sort_unstable();
+
+    let old_assignment = customer_assignment(old);
+    let new_assignment = customer_assignment(new);
+
+    let mut customers_moved: Vec<(usize, usize, usize)> = old_assignment
+        .iter()
+        .filter_map(|(&customer_id, &from_vehicle)| {
+            new_assignment.get(&customer_id).and_then(|&to_vehicle| {
+                (to_vehicle != from_vehicle).then_some((customer_id, from_vehicle, to_vehicle))
+            })
+        })
+        .collect();
+    customers_moved.sort_unstable();
+
+    SolutionDiff {
+        routes_added,
+        routes_removed,
+        customers_moved,
+        distance_delta: new.total_distance - old.total_distance,
+        duration_delta: new.total_duration - old.total_duration,
+        vehicles_delta: new.num_vehicles_used as i64 - old.num_vehicles_used as i64,
+    }
+}
+
+/// Maps each served customer id to the vehicle id of the route serving it.
+fn customer_assignment(solution: &Solution) -> HashMap<usize, usize> {
+    let mut assignment = HashMap::new();
+    for route in &solution.routes {
+        for &customer_id in &route.locations {
+            assignment.insert(customer_id, route.vehicle_id);
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Route;
+
+    fn route(vehicle_id: usize, locations: Vec<usize>) -> Route {
+        let mut route = Route::new(vehicle_id);
+        route.locations = locations;
+        route
+    }
+
+    #[test]
+    fn test_diff_detects_moved_customer_and_route_changes() {
+        let old = Solution {
+            routes: vec![route(0, vec![1, 2]), route(1, vec![3])],
+            total_distance: 100.0,
+            total_duration: 200.0,
+            num_vehicles_used: 2,
+        };
+        let new = Solution {
+            routes: vec![route(0, vec![1]), route(2, vec![2, 3])],
+            total_distance: 90.0,
+            total_duration: 180.0,
+            num_vehicles_used: 2,
+        };
+
+        let diff = diff_solutions(&old, &new);
+        assert_eq!(diff.routes_added, vec![2]);
+        assert_eq!(diff.routes_removed, vec![1]);
+        assert_eq!(diff.customers_moved, vec![(2, 0, 2), (3, 1, 2)]);
+        assert_eq!(diff.distance_delta, -10.0);
+        assert_eq!(diff.duration_delta, -20.0);
+        assert_eq!(diff.vehicles_delta, 0);
+    }
+
+    #[test]
+    fn test_diff_identical_solutions_is_empty() {
+        let solution = Solution {
+            routes: vec![route(0, vec![1, 2])],
+            total_distance: 50.0,
+            total_duration: 75.0,
+            num_vehicles_used: 1,
+        };
+
+        let diff = diff_solutions(&solution, &solution);
+        assert!(diff.routes_added.is_empty());
+        assert!(diff.routes_removed.is_empty());
+        assert!(diff.customers_moved.is_empty());
+        assert_eq!(diff.distance_delta, 0.0);
+        assert_eq!(diff.duration_delta, 0.0);
+        assert_eq!(diff.vehicles_delta, 0);
+    }
+}
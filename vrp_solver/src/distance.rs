@@ -51,6 +51,14 @@ pub enum DistanceMethod {
     Haversine,
     Manhattan,
     Euclidean,
+    /// Route through a previously-built road network graph, identified by
+    /// the UUID it was cached under (see `AppState::road_graphs`). This
+    /// variant exists so callers can express intent through the regular
+    /// `DistanceMethod` enum; the actual graph lookup and many-to-many
+    /// routing happens in `routing::build_road_network_matrix`, since
+    /// `calculate_distance_matrix` has no access to the graph cache. Picking
+    /// it here falls back to haversine with a logged warning.
+    RoadNetwork { graph_id: uuid::Uuid },
 }
 
 /// Calculate distance matrix using parallel processing
@@ -58,8 +66,15 @@ pub fn calculate_distance_matrix(
     instance: &mut VrpInstance,
     method: DistanceMethod,
 ) -> &Vec<Vec<f64>> {
+    if let DistanceMethod::RoadNetwork { graph_id } = method {
+        eprintln!(
+            "Warning: DistanceMethod::RoadNetwork({}) requires routing::build_road_network_matrix; falling back to haversine",
+            graph_id
+        );
+    }
+
     let n = instance.locations.len();
-    
+
     // Create coordinate pairs for parallel processing by copying coordinates
     let locations = &instance.locations;
     let coord_pairs: Vec<(usize, usize, Coordinate, Coordinate)> = (0..n)
@@ -81,6 +96,7 @@ pub fn calculate_distance_matrix(
                     DistanceMethod::Haversine => haversine_distance(coord1, coord2),
                     DistanceMethod::Manhattan => manhattan_distance(coord1, coord2),
                     DistanceMethod::Euclidean => euclidean_distance(coord1, coord2),
+                    DistanceMethod::RoadNetwork { .. } => haversine_distance(coord1, coord2),
                 }
             };
             (i, j, distance)
@@ -127,6 +143,39 @@ pub fn calculate_time_matrix(
     &instance.time_matrix
 }
 
+/// Install an externally supplied distance matrix (and optional duration
+/// matrix) onto an instance, bypassing coordinate-based calculation. This
+/// lets callers plug in real road-network output (e.g. OSRM/Valhalla) while
+/// still using all downstream routing/savings functions.
+pub fn set_distance_matrix(
+    instance: &mut VrpInstance,
+    distance_matrix: Vec<Vec<f64>>,
+    duration_matrix: Option<Vec<Vec<f64>>>,
+) -> crate::VrpResult<()> {
+    let n = instance.locations.len();
+
+    if distance_matrix.len() != n || distance_matrix.iter().any(|row| row.len() != n) {
+        return Err(crate::VrpError::InvalidInput(format!(
+            "Distance matrix must be {0}x{0} to match {0} locations",
+            n
+        )));
+    }
+
+    if let Some(ref duration_matrix) = duration_matrix {
+        if duration_matrix.len() != n || duration_matrix.iter().any(|row| row.len() != n) {
+            return Err(crate::VrpError::InvalidInput(format!(
+                "Duration matrix must be {0}x{0} to match {0} locations",
+                n
+            )));
+        }
+    }
+
+    instance.distance_matrix = distance_matrix;
+    instance.time_matrix = duration_matrix;
+
+    Ok(())
+}
+
 /// Calculate nearest neighbors for each location using parallel processing
 pub fn calculate_nearest_neighbors(
     instance: &VrpInstance,
@@ -306,4 +355,28 @@ mod tests {
         assert!(instance.distance_matrix[0][1] > 0.0);
         assert_eq!(instance.distance_matrix[0][1], instance.distance_matrix[1][0]);
     }
+
+    #[test]
+    fn test_set_distance_matrix_rejects_wrong_shape() {
+        let locations = vec![
+            crate::types::Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            crate::types::Location::new(
+                1,
+                "Customer 1".to_string(),
+                Coordinate::new(1.0, 1.0),
+                10.0,
+                None,
+                5.0,
+            ),
+        ];
+        let vehicles = vec![crate::types::Vehicle::new(0, 100.0, None, None, 0)];
+        let mut instance = VrpInstance::new(locations, vehicles);
+
+        let bad_matrix = vec![vec![0.0, 1.0, 2.0]];
+        assert!(set_distance_matrix(&mut instance, bad_matrix, None).is_err());
+
+        let good_matrix = vec![vec![0.0, 42.0], vec![42.0, 0.0]];
+        assert!(set_distance_matrix(&mut instance, good_matrix, None).is_ok());
+        assert_eq!(instance.distance_matrix[0][1], 42.0);
+    }
 }
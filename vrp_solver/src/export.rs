@@ -0,0 +1,174 @@
+//! Serializers for rendering solved VRP solutions on a map
+
+use crate::types::{Solution, VrpInstance};
+use geojson::{Feature, FeatureCollection, GeoJson, Geometry, Value};
+use serde_json::Map;
+
+/// A small fixed palette so consecutive routes are visually distinguishable
+/// on a map without requiring the caller to pick colors.
+const ROUTE_COLORS: &[&str] = &[
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#46f0f0", "#f032e6",
+    "#bcf60c", "#fabebe", "#008080", "#9a6324", "#800000", "#aaffc3", "#808000",
+];
+
+/// Turn a solved [`Solution`] into a GeoJSON `FeatureCollection` suitable for
+/// dropping straight onto Leaflet/Mapbox: one `LineString` feature per route
+/// (depot -> stops -> depot) plus one `Point` feature per stop.
+pub fn solution_to_geojson(solution: &Solution, instance: &VrpInstance) -> GeoJson {
+    build_geojson(solution, instance, None)
+}
+
+/// Like [`solution_to_geojson`], but each route's `LineString` follows real
+/// roads (e.g. from `routing::RoadGraph`) instead of straight depot-stop-depot
+/// hops. `road_geometry[route_idx]` is the coordinate sequence (as `[lon, lat]`
+/// pairs) for `solution.routes[route_idx]`; a missing or too-short entry falls
+/// back to the straight-line geometry for that route.
+pub fn solution_to_geojson_with_road_geometry(
+    solution: &Solution,
+    instance: &VrpInstance,
+    road_geometry: &[Vec<[f64; 2]>],
+) -> GeoJson {
+    build_geojson(solution, instance, Some(road_geometry))
+}
+
+fn build_geojson(
+    solution: &Solution,
+    instance: &VrpInstance,
+    road_geometry: Option<&[Vec<[f64; 2]>]>,
+) -> GeoJson {
+    let mut features = Vec::new();
+
+    for (route_idx, route) in solution.routes.iter().enumerate() {
+        let stroke = ROUTE_COLORS[route_idx % ROUTE_COLORS.len()];
+        let depot_id = instance
+            .get_vehicle(route.vehicle_id)
+            .map(|v| v.depot_id);
+
+        let road_coords = road_geometry
+            .and_then(|geometry| geometry.get(route_idx))
+            .filter(|coords| coords.len() >= 2);
+
+        let coordinates: Vec<Vec<f64>> = if let Some(coords) = road_coords {
+            coords.iter().map(|c| vec![c[0], c[1]]).collect()
+        } else {
+            let mut coordinates = Vec::new();
+            if let Some(depot) = depot_id.and_then(|id| instance.get_location(id)) {
+                coordinates.push(vec![depot.coordinate.lon, depot.coordinate.lat]);
+            }
+            for &location_id in &route.locations {
+                if let Some(location) = instance.get_location(location_id) {
+                    coordinates.push(vec![location.coordinate.lon, location.coordinate.lat]);
+                }
+            }
+            if let Some(depot) = depot_id.and_then(|id| instance.get_location(id)) {
+                coordinates.push(vec![depot.coordinate.lon, depot.coordinate.lat]);
+            }
+            coordinates
+        };
+
+        if coordinates.len() >= 2 {
+            let geometry = Geometry::new(Value::LineString(coordinates));
+
+            let mut properties = Map::new();
+            properties.insert("vehicle_id".to_string(), serde_json::Value::Number(route.vehicle_id.into()));
+            if let Some(distance_num) = serde_json::Number::from_f64(route.total_distance) {
+                properties.insert("route_distance".to_string(), serde_json::Value::Number(distance_num));
+            }
+            if let Some(duration_num) = serde_json::Number::from_f64(route.total_duration) {
+                properties.insert("route_duration".to_string(), serde_json::Value::Number(duration_num));
+            }
+            if let Some(load_num) = serde_json::Number::from_f64(route.total_demand) {
+                properties.insert("load".to_string(), serde_json::Value::Number(load_num));
+            }
+            properties.insert("stroke".to_string(), serde_json::Value::String(stroke.to_string()));
+
+            features.push(Feature {
+                bbox: None,
+                geometry: Some(geometry),
+                id: None,
+                properties: Some(properties),
+                foreign_members: None,
+            });
+        }
+
+        for &location_id in &route.locations {
+            if let Some(location) = instance.get_location(location_id) {
+                let geometry = Geometry::new(Value::Point(vec![location.coordinate.lon, location.coordinate.lat]));
+
+                let mut properties = Map::new();
+                properties.insert("id".to_string(), serde_json::Value::Number(location.id.into()));
+                properties.insert("name".to_string(), serde_json::Value::String(location.name.clone()));
+                properties.insert("vehicle_id".to_string(), serde_json::Value::Number(route.vehicle_id.into()));
+                if let Some(demand_num) = serde_json::Number::from_f64(location.demand) {
+                    properties.insert("demand".to_string(), serde_json::Value::Number(demand_num));
+                }
+                properties.insert("stroke".to_string(), serde_json::Value::String(stroke.to_string()));
+
+                features.push(Feature {
+                    bbox: None,
+                    geometry: Some(geometry),
+                    id: None,
+                    properties: Some(properties),
+                    foreign_members: None,
+                });
+            }
+        }
+    }
+
+    GeoJson::FeatureCollection(FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    })
+}
+
+/// Flatten a solved [`Solution`] into a CSV with one row per stop:
+/// `route_index, stop_order, location_id, name, lat, lon, demand,
+/// arrival_time, service_time`. `arrival_time` is left blank for a route
+/// whose schedule can't be computed (no time matrix on `instance`).
+pub fn solution_to_csv(solution: &Solution, instance: &VrpInstance) -> String {
+    let mut csv = String::from(
+        "route_index,stop_order,location_id,name,lat,lon,demand,arrival_time,service_time\n",
+    );
+    let validator = crate::validate::RouteValidator::new();
+
+    for (route_idx, route) in solution.routes.iter().enumerate() {
+        let schedule = validator.compute_schedule(instance, route).ok();
+
+        for (stop_order, &location_id) in route.locations.iter().enumerate() {
+            let Some(location) = instance.get_location(location_id) else {
+                continue;
+            };
+
+            let arrival_time = schedule
+                .as_ref()
+                .and_then(|schedule| schedule.stops.get(stop_order))
+                .map(|entry| entry.arrival.to_string())
+                .unwrap_or_default();
+
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{}\n",
+                route_idx,
+                stop_order,
+                location.id,
+                csv_escape(&location.name),
+                location.coordinate.lat,
+                location.coordinate.lon,
+                location.demand,
+                arrival_time,
+                location.service_time,
+            ));
+        }
+    }
+
+    csv
+}
+
+/// Quote a CSV field if it contains a comma, quote, or newline.
+fn csv_escape(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -2,7 +2,7 @@
 
 use axum::{
     extract::{Path, Query, State, Multipart},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
@@ -16,13 +16,18 @@ use std::io::Write;
 use crate::{
     api_types::*,
     app_state::AppState,
-    osm_parser::OsmParser,
+    job::{JobProgress, JobStatus},
+    osm_parser::{OsmParser, GraphSource, PostgisSource},
     solver::*,
     distance::DistanceMethod,
     utils::VrpInstanceBuilder,
+    validate::RouteValidator,
+    diff::diff_solutions,
     types::*,
+    termination::{TerminationConfig, TerminationReason},
     VrpError,
 };
+use std::sync::atomic::AtomicBool;
 
 // Error handling for handlers
 impl IntoResponse for VrpError {
@@ -118,11 +123,17 @@ pub fn create_routes() -> Router<AppState> {
         .route("/health", get(health_check))
         .route("/stats", get(get_stats))
         .route("/osm/upload", post(upload_osm))
+        .route("/vrp/import-tsplib", post(import_tsplib))
         .route("/vrp/map", post(map_locations))
         .route("/vrp/generate", post(generate_vrp))
+        .route("/vrp/matrix", post(set_matrix))
         .route("/vrp/solve", post(solve_vrp))
+        .route("/vrp/job/:job_id", get(get_job_status).delete(cancel_job))
+        .route("/vrp/solution/diff", post(diff_solutions_handler))
         .route("/vrp/solution/:solution_id", get(get_solution))
         .route("/vrp/solution/:solution_id/export", get(export_solution))
+        .route("/vrp/solution/:solution_id/geojson", get(get_solution_geojson))
+        .route("/vrp/solution/:solution_id/osrm", get(get_solution_osrm))
 }
 
 // Health check endpoint
@@ -147,7 +158,22 @@ async fn get_stats(State(state): State<AppState>) -> Result<Json<crate::app_stat
     Ok(Json(stats))
 }
 
-// OSM Upload endpoint - handles both file upload and URL
+/// Parses a `"south,west,north,east"` bbox field into `OsmParser::parse_from_overpass`'s tuple form.
+fn parse_bbox(input: &str) -> Result<(f64, f64, f64, f64), HandlerError> {
+    let parts: Vec<&str> = input.split(',').map(str::trim).collect();
+    if parts.len() != 4 {
+        return Err(HandlerError::ParseError(format!(
+            "bbox must be \"south,west,north,east\", got: {}",
+            input
+        )));
+    }
+    let parse = |value: &str| {
+        value.parse::<f64>().map_err(|_| HandlerError::ParseError(format!("Invalid bbox coordinate: {}", value)))
+    };
+    Ok((parse(parts[0])?, parse(parts[1])?, parse(parts[2])?, parse(parts[3])?))
+}
+
+// OSM Upload endpoint - handles both file upload, URL, and Overpass bbox
 async fn upload_osm(
     State(state): State<AppState>,
     mut multipart: Multipart,
@@ -156,13 +182,16 @@ async fn upload_osm(
 
     let mut file_data: Option<Vec<u8>> = None;
     let mut file_url: Option<String> = None;
+    let mut bbox_input: Option<String> = None;
+    let mut postgis_input: Option<String> = None;
+    let mut profile = VehicleProfile::default();
 
     // Process multipart form data
     while let Some(field) = multipart.next_field().await.map_err(|e| {
         HandlerError::ParseError(format!("Failed to read multipart data: {}", e))
     })? {
         let name = field.name().unwrap_or("").to_string();
-        
+
         match name.as_str() {
             "file" => {
                 let data = field.bytes().await.map_err(|e| {
@@ -175,53 +204,88 @@ async fn upload_osm(
                     HandlerError::ParseError(format!("Failed to read URL: {}", e))
                 })?);
             }
+            "bbox" => {
+                bbox_input = Some(field.text().await.map_err(|e| {
+                    HandlerError::ParseError(format!("Failed to read bbox: {}", e))
+                })?);
+            }
+            "postgis" => {
+                postgis_input = Some(field.text().await.map_err(|e| {
+                    HandlerError::ParseError(format!("Failed to read postgis descriptor: {}", e))
+                })?);
+            }
+            "profile" => {
+                let text = field.text().await.map_err(|e| {
+                    HandlerError::ParseError(format!("Failed to read profile: {}", e))
+                })?;
+                profile = match text.as_str() {
+                    "driving" => VehicleProfile::Driving,
+                    "cycling" => VehicleProfile::Cycling,
+                    "walking" => VehicleProfile::Walking,
+                    other => return Err(HandlerError::ParseError(format!("Unknown profile: {}", other))),
+                };
+            }
             _ => {
                 // Ignore unknown fields
             }
         }
     }
 
-    // Handle file upload or URL download
-    let (_temp_file, temp_file_path) = if let Some(data) = file_data {
+    let mut parser = OsmParser::new();
+
+    // Handle file upload, URL download, or a live Overpass bbox query
+    if let Some(data) = file_data {
         info!("Processing uploaded file ({} bytes)", data.len());
-        
+
         let mut temp_file = NamedTempFile::new()
             .map_err(|e| HandlerError::InternalError(format!("Failed to create temp file: {}", e)))?;
-        
+
         temp_file.write_all(&data)
             .map_err(|e| HandlerError::InternalError(format!("Failed to write temp file: {}", e)))?;
-        
-        let temp_path = temp_file.path().to_string_lossy().to_string();
-        (Some(temp_file), temp_path)
+
+        parser.parse_pbf_file(&temp_file.path().to_string_lossy())
+            .map_err(|e| HandlerError::InternalError(format!("Failed to parse OSM file: {}", e)))?;
     } else if let Some(url) = file_url {
         info!("Downloading OSM data from URL: {}", url);
-        
+
         // Download file from URL
         let response = reqwest::get(&url).await
             .map_err(|e| HandlerError::InternalError(format!("Failed to download file: {}", e)))?;
-        
+
         let data = response.bytes().await
             .map_err(|e| HandlerError::InternalError(format!("Failed to read downloaded data: {}", e)))?;
-        
+
         let mut temp_file = NamedTempFile::new()
             .map_err(|e| HandlerError::InternalError(format!("Failed to create temp file: {}", e)))?;
-        
+
         temp_file.write_all(&data)
             .map_err(|e| HandlerError::InternalError(format!("Failed to write temp file: {}", e)))?;
-        
-        let temp_path = temp_file.path().to_string_lossy().to_string();
-        (Some(temp_file), temp_path)
-    } else {
-        return Err(HandlerError::ParseError("No file or URL provided".to_string()));
-    };
 
-    // Parse OSM data
-    let mut parser = OsmParser::new();
-    parser.parse_pbf_file(&temp_file_path)
-        .map_err(|e| HandlerError::InternalError(format!("Failed to parse OSM file: {}", e)))?;
+        parser.parse_pbf_file(&temp_file.path().to_string_lossy())
+            .map_err(|e| HandlerError::InternalError(format!("Failed to parse OSM file: {}", e)))?;
+    } else if let Some(bbox_str) = bbox_input {
+        let bbox = parse_bbox(&bbox_str)?;
+        info!("Fetching OSM data from Overpass for bbox {:?}", bbox);
+
+        parser.parse_from_overpass(bbox, &["highway"], None).await
+            .map_err(|e| HandlerError::InternalError(format!("Failed to fetch OSM data from Overpass: {}", e)))?;
+    } else if let Some(postgis_json) = postgis_input {
+        info!("Loading road network from PostGIS");
+
+        let source: PostgisSource = serde_json::from_str(&postgis_json)
+            .map_err(|e| HandlerError::ParseError(format!("Invalid postgis descriptor: {}", e)))?;
+
+        let data = source.load().await
+            .map_err(|e| HandlerError::InternalError(format!("Failed to load PostGIS graph: {}", e)))?;
 
-    // Filter to roads only
-    parser.filter_roads_only();
+        parser = OsmParser::from_data(data);
+    } else {
+        return Err(HandlerError::ParseError("No file, URL, bbox, or postgis descriptor provided".to_string()));
+    }
+
+    // Filter to roads usable by the requested vehicle profile (driving by
+    // default, if the upload didn't specify one)
+    parser.filter_roads_for_profile(profile);
 
     let node_count = parser.data.nodes.len();
     let way_count = parser.data.ways.len();
@@ -251,6 +315,92 @@ async fn upload_osm(
     }))
 }
 
+// Import a TSPLIB/CVRPLIB problem file as a ready-to-solve VRP instance
+async fn import_tsplib(
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Json<GenerateVrpResponse>, HandlerError> {
+    info!("Received TSPLIB import request");
+
+    let mut file_text: Option<String> = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        HandlerError::ParseError(format!("Failed to read multipart data: {}", e))
+    })? {
+        let name = field.name().unwrap_or("").to_string();
+
+        if name == "file" {
+            file_text = Some(field.text().await.map_err(|e| {
+                HandlerError::ParseError(format!("Failed to read file data: {}", e))
+            })?);
+        }
+    }
+
+    let file_text = file_text
+        .ok_or_else(|| HandlerError::ParseError("No file provided".to_string()))?;
+
+    let instance = crate::tsplib::parse_tsplib_str(&file_text)?;
+
+    let depot_id = instance.vehicles.first().map(|v| v.depot_id).unwrap_or(0);
+    let depot_location = instance.get_location(depot_id)
+        .ok_or_else(|| HandlerError::InternalError("TSPLIB instance has no depot location".to_string()))?;
+
+    let mapped_depot = MappedLocation {
+        node_id: 0,
+        lat: depot_location.coordinate.lat,
+        lon: depot_location.coordinate.lon,
+        distance_to_original: 0.0,
+    };
+    let mapped_customers: Vec<MappedLocation> = instance.locations.iter()
+        .filter(|location| location.id != depot_id)
+        .map(|location| MappedLocation {
+            node_id: 0,
+            lat: location.coordinate.lat,
+            lon: location.coordinate.lon,
+            distance_to_original: 0.0,
+        })
+        .collect();
+
+    // TSPLIB instances have no source OSM graph to key road-network features
+    // (e.g. `?geometry=roads` exports) off of, so `graph_id` is a standalone
+    // id rather than one pointing at a real `StoredGraph`.
+    let mapping = StoredMapping {
+        graph_id: Uuid::new_v4(),
+        depot: mapped_depot,
+        customers: mapped_customers,
+        created_at: SystemTime::now(),
+    };
+
+    let customers = instance.locations.len().saturating_sub(1);
+    let vehicles = instance.vehicles.len();
+
+    let vrp_id = Uuid::new_v4();
+    let stored_instance = StoredVrpInstance {
+        id: vrp_id,
+        mapping,
+        instance,
+        constraints: VrpConstraints {
+            time_windows: false,
+            max_distance: None,
+            max_duration: None,
+            service_time: None,
+        },
+        created_at: SystemTime::now(),
+    };
+
+    state.store_vrp_instance(stored_instance)
+        .map_err(|e| HandlerError::StateError(e))?;
+
+    info!("Imported TSPLIB instance {} with {} customers and {} vehicles", vrp_id, customers, vehicles);
+
+    Ok(Json(GenerateVrpResponse {
+        vrp_id,
+        customers,
+        vehicles,
+        depot_count: 1,
+    }))
+}
+
 // Map depot and customer locations to OSM nodes
 async fn map_locations(
     State(state): State<AppState>,
@@ -263,11 +413,19 @@ async fn map_locations(
         .map_err(|e| HandlerError::StateError(e))?
         .ok_or_else(|| HandlerError::NotFound(format!("Graph {} not found", request.graph_id)))?;
 
-    let parser = OsmParser { data: stored_graph.osm_data.clone() };
+    // Building from `from_data` sets up the parser's R-tree immediately, so
+    // the depot + every customer below snap against it instead of each
+    // doing its own O(n) scan over every graph node.
+    let parser = OsmParser::from_data(stored_graph.osm_data.clone());
 
-    // Map depot location
     let depot_coord = Coordinate::from(&request.depot);
-    let (depot_node_id, depot_distance) = parser.find_nearest_node(depot_coord.lat, depot_coord.lon)
+    let customer_coords: Vec<Coordinate> = request.customers.iter().map(Coordinate::from).collect();
+    let mut query_coords = vec![depot_coord];
+    query_coords.extend(customer_coords);
+    let snapped = parser.snap_locations(&query_coords);
+
+    // Map depot location
+    let (depot_node_id, depot_distance) = snapped[0]
         .ok_or_else(|| HandlerError::InternalError("No nodes found in graph".to_string()))?;
 
     let (depot_lat, depot_lon) = parser.get_node_coordinates(depot_node_id)
@@ -282,9 +440,8 @@ async fn map_locations(
 
     // Map customer locations
     let mut mapped_customers = Vec::new();
-    for customer in &request.customers {
-        let coord = Coordinate::from(customer);
-        let (node_id, distance) = parser.find_nearest_node(coord.lat, coord.lon)
+    for &snapped_customer in &snapped[1..] {
+        let (node_id, distance) = snapped_customer
             .ok_or_else(|| HandlerError::InternalError("No nodes found for customer".to_string()))?;
 
         let (lat, lon) = parser.get_node_coordinates(node_id)
@@ -355,26 +512,58 @@ async fn generate_vrp(
     }
 
     // Add vehicles
+    let profile = request.profile.unwrap_or_default();
     for i in 0..request.vehicles {
         let mut vehicle = Vehicle::new(i, request.capacity, None, None, 0);
-        
+        vehicle.profile = profile;
+
+        if let Some(capacity_dims) = &request.capacity_dims {
+            vehicle = vehicle.with_capacity_dims(capacity_dims.clone());
+        }
+
         if let Some(max_distance) = request.constraints.max_distance {
             vehicle.max_distance = Some(max_distance);
         }
-        
+
         if let Some(max_duration) = request.constraints.max_duration {
             vehicle.max_duration = Some(max_duration);
         }
-        
+
         builder = builder.add_vehicle(vehicle);
     }
 
-    // Build the VRP instance
-    let instance = builder
+    // Build the VRP instance. Road-network distances (requested via
+    // `"distance": "road"`) are layered on afterward since they need the graph's
+    // OSM data and cached `RoadGraph`, neither of which `VrpInstanceBuilder`
+    // has access to.
+    let average_speed_ms = profile.default_average_speed_ms();
+    let mut instance = builder
         .with_distance_method(DistanceMethod::Haversine)
-        .with_average_speed(15.0) // 15 m/s ≈ 54 km/h
+        .with_average_speed(average_speed_ms)
         .build()?;
 
+    if request.distance.as_deref() == Some("road") {
+        let stored_graph = state.get_graph(&request.graph_id)
+            .map_err(|e| HandlerError::StateError(e))?
+            .ok_or_else(|| HandlerError::NotFound(format!("Graph {} not found", request.graph_id)))?;
+        let road_graph = state.get_or_build_road_graph(&request.graph_id)
+            .map_err(|e| HandlerError::StateError(e))?;
+
+        let road_matrix = crate::routing::build_road_network_matrix(
+            &mut instance,
+            &stored_graph.osm_data,
+            &road_graph,
+            Some(average_speed_ms),
+        )?;
+
+        if !road_matrix.disconnected_locations.is_empty() {
+            info!(
+                "{} location(s) disconnected from the road graph; fell back to haversine for those pairs",
+                road_matrix.disconnected_locations.len()
+            );
+        }
+    }
+
     let customers = mapping.customers.len();
     let vehicles = request.vehicles;
 
@@ -401,11 +590,122 @@ async fn generate_vrp(
     }))
 }
 
+// Install a precomputed distance (and optional duration) matrix on a VRP instance
+async fn set_matrix(
+    State(state): State<AppState>,
+    Json(request): Json<SetDistanceMatrixRequest>,
+) -> Result<Json<SetDistanceMatrixResponse>, HandlerError> {
+    info!("Setting precomputed distance matrix for VRP instance: {}", request.vrp_id);
+
+    let mut stored_instance = state.get_vrp_instance(&request.vrp_id)
+        .map_err(|e| HandlerError::StateError(e))?
+        .ok_or_else(|| HandlerError::NotFound(format!("VRP instance {} not found", request.vrp_id)))?;
+
+    let size = request.distance_matrix.len();
+    let has_duration_matrix = request.duration_matrix.is_some();
+
+    crate::distance::set_distance_matrix(
+        &mut stored_instance.instance,
+        request.distance_matrix,
+        request.duration_matrix,
+    )?;
+
+    state.store_vrp_instance(stored_instance)
+        .map_err(|e| HandlerError::StateError(e))?;
+
+    info!("Installed {}x{} distance matrix for VRP instance {}", size, size, request.vrp_id);
+
+    Ok(Json(SetDistanceMatrixResponse {
+        vrp_id: request.vrp_id,
+        size,
+        has_duration_matrix,
+        message: "Distance matrix installed".to_string(),
+    }))
+}
+
+/// Run `algorithm` against `instance`. Termination settings only apply to
+/// MultiStart and Metaheuristic, since those are the only solvers that can
+/// usefully be re-run to track convergence; `stop`/`on_progress` are only
+/// consulted by Metaheuristic (the only solver slow enough to need either),
+/// and are `None`/no-op for a synchronous solve.
+fn run_solve(
+    instance: &VrpInstance,
+    algorithm: &SolverAlgorithm,
+    termination: &Option<TerminationSettings>,
+    max_generations: Option<usize>,
+    max_time_ms: Option<u64>,
+    min_cv: Option<f64>,
+    stop: Option<&AtomicBool>,
+    on_progress: impl FnMut(f64, usize),
+) -> Result<(Solution, Option<TerminationReason>, Option<usize>), VrpError> {
+    match (algorithm, termination) {
+        (SolverAlgorithm::MultiStart, Some(settings)) => {
+            let solver = MultiStartSolver::new().with_default_solvers();
+            let (solution, reason, iterations) = solver.solve_with_termination(
+                instance,
+                settings.clone().into(),
+            )?;
+            Ok((solution, Some(reason), Some(iterations)))
+        }
+        (SolverAlgorithm::Metaheuristic, _) => {
+            let defaults = TerminationConfig::default();
+            let config = TerminationConfig {
+                max_iterations: max_generations.or(defaults.max_iterations),
+                max_time_secs: max_time_ms.map(|ms| ms as f64 / 1000.0).or(defaults.max_time_secs),
+                min_cv: min_cv.or(defaults.min_cv),
+                // A metaheuristic's per-generation improvement is noisier
+                // than a constructive solver's, so convergence needs a much
+                // wider window before it's trustworthy.
+                window_size: 200,
+            };
+            let solver = MetaheuristicSolver::new();
+            let (solution, reason, iterations) = match stop {
+                Some(stop) => solver.solve_with_progress(instance, config, stop, on_progress)?,
+                None => solver.solve_with_termination(instance, config)?,
+            };
+            Ok((solution, Some(reason), Some(iterations)))
+        }
+        _ => {
+            let solver: Box<dyn VrpSolver + Sync> = match algorithm {
+                SolverAlgorithm::Greedy => Box::new(GreedyNearestNeighbor::new()),
+                SolverAlgorithm::GreedyFarthest => Box::new(GreedyNearestNeighbor::new().with_farthest_start(true)),
+                SolverAlgorithm::ClarkeWright => Box::new(ClarkeWrightSavings::new()),
+                SolverAlgorithm::MultiStart => Box::new(MultiStartSolver::new().with_default_solvers()),
+                SolverAlgorithm::SimulatedAnnealing => Box::new(SimulatedAnnealing::new()),
+                SolverAlgorithm::Metaheuristic => unreachable!("handled above"),
+            };
+            Ok((solver.solve(instance)?, None, None))
+        }
+    }
+}
+
+/// Build a `StoredSolution` from a finished solve and persist it, returning
+/// the new solution id. Shared by the synchronous and async `/vrp/solve` paths.
+fn store_solved_vrp(
+    state: &AppState,
+    vrp_id: Uuid,
+    algorithm: SolverAlgorithm,
+    solution: Solution,
+    solve_time_ms: f64,
+) -> Result<Uuid, String> {
+    let solution_id = Uuid::new_v4();
+    let stored_solution = StoredSolution {
+        id: solution_id,
+        vrp_id,
+        solution,
+        algorithm,
+        solve_time_ms,
+        created_at: SystemTime::now(),
+    };
+    state.store_solution(stored_solution)?;
+    Ok(solution_id)
+}
+
 // Solve VRP instance
 async fn solve_vrp(
     State(state): State<AppState>,
     Json(request): Json<SolveVrpRequest>,
-) -> Result<Json<SolveVrpResponse>, HandlerError> {
+) -> Result<Response, HandlerError> {
     info!("Solving VRP instance {} with algorithm {:?}", request.vrp_id, request.algorithm);
 
     // Get the stored VRP instance
@@ -413,29 +713,79 @@ async fn solve_vrp(
         .map_err(|e| HandlerError::StateError(e))?
         .ok_or_else(|| HandlerError::NotFound(format!("VRP instance {} not found", request.vrp_id)))?;
 
-    // Select and create solver
-    let solver: Box<dyn VrpSolver + Sync> = match request.algorithm {
-        SolverAlgorithm::Greedy => Box::new(GreedyNearestNeighbor::new()),
-        SolverAlgorithm::GreedyFarthest => Box::new(GreedyNearestNeighbor::new().with_farthest_start(true)),
-        SolverAlgorithm::ClarkeWright => Box::new(ClarkeWrightSavings::new()),
-        SolverAlgorithm::MultiStart => Box::new(MultiStartSolver::new().with_default_solvers()),
-    };
+    if request.async_mode {
+        let (job_id, job) = state.create_job().map_err(|e| HandlerError::StateError(e))?;
+
+        let state = state.clone();
+        tokio::spawn(async move {
+            job.set_status(JobStatus::Running);
+            let start_time = Instant::now();
+
+            let progress_job = job.clone();
+            let result = run_solve(
+                &stored_instance.instance,
+                &request.algorithm,
+                &request.termination,
+                request.max_generations,
+                request.max_time_ms,
+                request.min_cv,
+                Some(job.stop_flag()),
+                move |best_cost, generations| {
+                    progress_job.set_progress(JobProgress {
+                        best_cost: Some(best_cost),
+                        elapsed_ms: start_time.elapsed().as_secs_f64() * 1000.0,
+                        generations,
+                    });
+                },
+            );
+
+            match result {
+                Ok((solution, _reason, _iterations)) => {
+                    let solve_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
+                    match store_solved_vrp(&state, request.vrp_id, request.algorithm, solution, solve_time_ms) {
+                        Ok(solution_id) => job.finish(solution_id),
+                        Err(e) => job.fail(e),
+                    }
+                }
+                Err(e) => job.fail(e.to_string()),
+            }
+        });
+
+        return Ok(Json(SolveJobQueuedResponse { job_id, status: JobStatus::Queued }).into_response());
+    }
 
-    // Solve the VRP
     let start_time = Instant::now();
-    let solution = solver.solve(&stored_instance.instance)?;
-    let solve_time = start_time.elapsed();
-    let solve_time_ms = solve_time.as_secs_f64() * 1000.0;
+    let (solution, termination_reason, iterations) = run_solve(
+        &stored_instance.instance,
+        &request.algorithm,
+        &request.termination,
+        request.max_generations,
+        request.max_time_ms,
+        request.min_cv,
+        None,
+        |_, _| {},
+    )?;
+    let solve_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
 
     info!("Solved VRP in {:.2}ms, found {} routes", solve_time_ms, solution.routes.len());
 
     // Convert to API format
     let api_routes = solution.routes.iter().map(|route| {
-        let locations: Vec<ApiLocation> = route.locations.iter()
+        let mut locations: Vec<ApiLocation> = route.locations.iter()
             .filter_map(|&loc_id| stored_instance.instance.get_location(loc_id))
             .map(ApiLocation::from)
             .collect();
 
+        // Time windows (and thus a schedule) are only meaningful when the
+        // instance carries a time matrix; fall back to no arrival/departure
+        // data otherwise, same as `validate_time_windows`'s own no-op.
+        if let Ok(schedule) = RouteValidator::new().compute_schedule(&stored_instance.instance, route) {
+            for (loc, entry) in locations.iter_mut().zip(schedule.stops.iter()) {
+                loc.arrival = Some(entry.arrival);
+                loc.departure = Some(entry.departure);
+            }
+        }
+
         ApiRoute {
             vehicle_id: route.vehicle_id,
             path: route.locations.clone(),
@@ -446,23 +796,12 @@ async fn solve_vrp(
         }
     }).collect();
 
-    // Store the solution
-    let solution_id = Uuid::new_v4();
-    let stored_solution = StoredSolution {
-        id: solution_id,
-        vrp_id: request.vrp_id,
-        solution,
-        algorithm: request.algorithm,
-        solve_time_ms,
-        created_at: SystemTime::now(),
-    };
-
-    let total_cost = stored_solution.solution.total_distance;
-    let total_distance = stored_solution.solution.total_distance;
-    let total_duration = stored_solution.solution.total_duration;
-    let vehicles_used = stored_solution.solution.num_vehicles_used;
+    let total_cost = solution.total_distance;
+    let total_distance = solution.total_distance;
+    let total_duration = solution.total_duration;
+    let vehicles_used = solution.num_vehicles_used;
 
-    state.store_solution(stored_solution)
+    let solution_id = store_solved_vrp(&state, request.vrp_id, request.algorithm, solution, solve_time_ms)
         .map_err(|e| HandlerError::StateError(e))?;
 
     Ok(Json(SolveVrpResponse {
@@ -473,6 +812,45 @@ async fn solve_vrp(
         total_duration,
         vehicles_used,
         solve_time_ms,
+        termination_reason,
+        iterations,
+    }).into_response())
+}
+
+// Poll the status and progress of a background solve job
+async fn get_job_status(
+    State(state): State<AppState>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<JobStatusResponse>, HandlerError> {
+    let job = state.get_job(&job_id)
+        .map_err(|e| HandlerError::StateError(e))?
+        .ok_or_else(|| HandlerError::NotFound(format!("Job {} not found", job_id)))?;
+
+    Ok(Json(JobStatusResponse {
+        status: job.status(),
+        progress: job.progress(),
+        solution_id: job.solution_id(),
+        error: job.error(),
+    }))
+}
+
+// Cancel a running background solve job
+async fn cancel_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<JobStatusResponse>, HandlerError> {
+    let job = state.get_job(&job_id)
+        .map_err(|e| HandlerError::StateError(e))?
+        .ok_or_else(|| HandlerError::NotFound(format!("Job {} not found", job_id)))?;
+
+    info!("Cancelling job {}", job_id);
+    job.cancel();
+
+    Ok(Json(JobStatusResponse {
+        status: job.status(),
+        progress: job.progress(),
+        solution_id: job.solution_id(),
+        error: job.error(),
     }))
 }
 
@@ -490,6 +868,37 @@ async fn get_solution(
     Ok(Json(solution))
 }
 
+// Diff two solutions for the same VRP instance (re-optimization workflows)
+async fn diff_solutions_handler(
+    State(state): State<AppState>,
+    Json(request): Json<DiffSolutionsRequest>,
+) -> Result<Json<DiffSolutionsResponse>, HandlerError> {
+    info!("Diffing solutions {} -> {} for VRP {}", request.old_solution_id, request.new_solution_id, request.vrp_id);
+
+    let old_solution = state.get_solution(&request.old_solution_id)
+        .map_err(|e| HandlerError::StateError(e))?
+        .ok_or_else(|| HandlerError::NotFound(format!("Solution {} not found", request.old_solution_id)))?;
+
+    let new_solution = state.get_solution(&request.new_solution_id)
+        .map_err(|e| HandlerError::StateError(e))?
+        .ok_or_else(|| HandlerError::NotFound(format!("Solution {} not found", request.new_solution_id)))?;
+
+    if old_solution.vrp_id != request.vrp_id || new_solution.vrp_id != request.vrp_id {
+        return Err(HandlerError::ParseError(format!(
+            "Solutions {} and {} must both belong to VRP instance {}",
+            request.old_solution_id, request.new_solution_id, request.vrp_id
+        )));
+    }
+
+    let diff = diff_solutions(&old_solution.solution, &new_solution.solution);
+
+    Ok(Json(DiffSolutionsResponse {
+        old_solution_id: request.old_solution_id,
+        new_solution_id: request.new_solution_id,
+        diff,
+    }))
+}
+
 // Export solution in different formats
 async fn export_solution(
     State(state): State<AppState>,
@@ -509,6 +918,18 @@ async fn export_solution(
     let format = params.format.as_deref().unwrap_or("json");
 
     match format {
+        "geojson" if params.geometry.as_deref() == Some("roads") => {
+            let road_geometry = state.get_or_build_road_geometry(&solution_id, || {
+                build_road_geometry(&state, &stored_solution.solution, &stored_vrp)
+            }).map_err(|e| HandlerError::StateError(e))?;
+
+            let geojson = crate::export::solution_to_geojson_with_road_geometry(
+                &stored_solution.solution,
+                &stored_vrp.instance,
+                &road_geometry,
+            );
+            Ok(Json(geojson).into_response())
+        }
         "geojson" => {
             let geojson = create_geojson_from_solution(&stored_solution.solution, &stored_vrp.instance)?;
             Ok(Json(geojson).into_response())
@@ -516,6 +937,28 @@ async fn export_solution(
         "json" => {
             Ok(Json(&stored_solution.solution).into_response())
         }
+        "osrm" => {
+            let precision = params.precision.unwrap_or(5);
+            let road_geometry = if params.geometry.as_deref() == Some("roads") {
+                Some(state.get_or_build_road_geometry(&solution_id, || {
+                    build_road_geometry(&state, &stored_solution.solution, &stored_vrp)
+                }).map_err(|e| HandlerError::StateError(e))?)
+            } else {
+                None
+            };
+
+            let osrm_response = crate::osrm::solution_to_osrm_export_with_road_geometry(
+                &stored_solution.solution,
+                &stored_vrp.instance,
+                road_geometry.as_deref().map(|g| g.as_slice()),
+                precision,
+            );
+            Ok(Json(osrm_response).into_response())
+        }
+        "csv" => {
+            let csv = crate::export::solution_to_csv(&stored_solution.solution, &stored_vrp.instance);
+            Ok(([(header::CONTENT_TYPE, "text/csv")], csv).into_response())
+        }
         _ => {
             Err(HandlerError::ParseError(format!("Unsupported export format: {}", format)))
         }
@@ -524,82 +967,136 @@ async fn export_solution(
 
 // Helper function to create GeoJSON from solution
 fn create_geojson_from_solution(
-    solution: &Solution, 
+    solution: &Solution,
     instance: &VrpInstance
 ) -> Result<geojson::GeoJson, HandlerError> {
-    use geojson::{GeoJson, Geometry, Value, Feature, FeatureCollection};
-    use serde_json::Map;
+    Ok(crate::export::solution_to_geojson(solution, instance))
+}
 
-    let mut features = Vec::new();
+/// Build one road-snapped coordinate sequence per route in `solution`, by
+/// walking `stored_vrp`'s mapped depot/customers through the road graph
+/// cached for their source OSM graph. Falls back to a straight hop between
+/// two locations wherever the road graph has no path between them.
+fn build_road_geometry(
+    state: &AppState,
+    solution: &Solution,
+    stored_vrp: &StoredVrpInstance,
+) -> Result<Vec<Vec<[f64; 2]>>, String> {
+    let graph_id = stored_vrp.mapping.graph_id;
+    let stored_graph = state.get_graph(&graph_id)?
+        .ok_or_else(|| format!("Graph {} not found", graph_id))?;
+    let road_graph = state.get_or_build_road_graph(&graph_id)?;
+
+    Ok(solution.routes.iter()
+        .map(|route| route_to_road_coordinates(
+            route,
+            &stored_vrp.instance,
+            &stored_vrp.mapping,
+            &stored_graph.osm_data,
+            &road_graph,
+        ))
+        .collect())
+}
 
-    // Add route features
-    for (route_idx, route) in solution.routes.iter().enumerate() {
-        let mut coordinates = Vec::new();
-        
-        for &location_id in &route.locations {
-            if let Some(location) = instance.get_location(location_id) {
-                coordinates.push(vec![location.coordinate.lon, location.coordinate.lat]);
-            }
-        }
+/// The OSM node a VRP location snapped to, following the depot=0,
+/// customer=index+1 id convention `generate_vrp` assigns.
+fn location_to_osm_node(mapping: &StoredMapping, location_id: usize) -> Option<i64> {
+    if location_id == 0 {
+        Some(mapping.depot.node_id)
+    } else {
+        mapping.customers.get(location_id - 1).map(|c| c.node_id)
+    }
+}
 
-        if coordinates.len() >= 2 {
-            let geometry = Geometry::new(Value::LineString(coordinates));
-            
-            let mut properties = Map::new();
-            properties.insert("route_id".to_string(), serde_json::Value::Number((route_idx + 1).into()));
-            properties.insert("vehicle_id".to_string(), serde_json::Value::Number(route.vehicle_id.into()));
-            if let Some(distance_num) = serde_json::Number::from_f64(route.total_distance) {
-                properties.insert("distance".to_string(), serde_json::Value::Number(distance_num));
-            }
-            if let Some(duration_num) = serde_json::Number::from_f64(route.total_duration) {
-                properties.insert("duration".to_string(), serde_json::Value::Number(duration_num));
-            }
-            if let Some(demand_num) = serde_json::Number::from_f64(route.total_demand) {
-                properties.insert("demand".to_string(), serde_json::Value::Number(demand_num));
-            }
+fn route_to_road_coordinates(
+    route: &Route,
+    instance: &VrpInstance,
+    mapping: &StoredMapping,
+    osm_data: &crate::osm_parser::OsmData,
+    road_graph: &crate::routing::RoadGraph,
+) -> Vec<[f64; 2]> {
+    let depot_id = instance.get_vehicle(route.vehicle_id).map(|v| v.depot_id);
+
+    let mut stop_ids = Vec::with_capacity(route.locations.len() + 2);
+    stop_ids.extend(depot_id);
+    stop_ids.extend(route.locations.iter().copied());
+    stop_ids.extend(depot_id);
+
+    let mut coordinates: Vec<[f64; 2]> = Vec::new();
+    for pair in stop_ids.windows(2) {
+        let (Some(from), Some(to)) = (instance.get_location(pair[0]), instance.get_location(pair[1])) else {
+            continue;
+        };
 
-            let feature = Feature {
-                bbox: None,
-                geometry: Some(geometry),
-                id: None,
-                properties: Some(properties),
-                foreign_members: None,
-            };
+        let segment: Vec<[f64; 2]> = match (
+            location_to_osm_node(mapping, pair[0]),
+            location_to_osm_node(mapping, pair[1]),
+        ) {
+            (Some(a), Some(b)) => road_graph.shortest_path(a, b)
+                .map(|path| path.iter()
+                    .filter_map(|node_id| osm_data.nodes.get(node_id))
+                    .map(|node| [node.lon, node.lat])
+                    .collect())
+                .unwrap_or_else(|| vec![
+                    [from.coordinate.lon, from.coordinate.lat],
+                    [to.coordinate.lon, to.coordinate.lat],
+                ]),
+            _ => vec![
+                [from.coordinate.lon, from.coordinate.lat],
+                [to.coordinate.lon, to.coordinate.lat],
+            ],
+        };
 
-            features.push(feature);
-        }
+        // Drop the first point of every segment after the first so shared
+        // endpoints between consecutive hops aren't duplicated.
+        let start = if coordinates.is_empty() { 0 } else { 1 };
+        coordinates.extend(segment.into_iter().skip(start));
     }
 
-    // Add location points
-    for location in &instance.locations {
-        let geometry = Geometry::new(Value::Point(vec![location.coordinate.lon, location.coordinate.lat]));
-        
-        let mut properties = Map::new();
-        properties.insert("id".to_string(), serde_json::Value::Number(location.id.into()));
-        properties.insert("name".to_string(), serde_json::Value::String(location.name.clone()));
-        if let Some(demand_num) = serde_json::Number::from_f64(location.demand) {
-            properties.insert("demand".to_string(), serde_json::Value::Number(demand_num));
-        }
-        properties.insert("type".to_string(), serde_json::Value::String(
-            if location.demand > 0.0 { "customer" } else { "depot" }.to_string()
-        ));
-
-        let feature = Feature {
-            bbox: None,
-            geometry: Some(geometry),
-            id: None,
-            properties: Some(properties),
-            foreign_members: None,
-        };
+    coordinates
+}
 
-        features.push(feature);
-    }
+// Get solution rendered as a GeoJSON FeatureCollection (routes + stops)
+async fn get_solution_geojson(
+    State(state): State<AppState>,
+    Path(solution_id): Path<Uuid>,
+) -> Result<Json<geojson::GeoJson>, HandlerError> {
+    info!("Rendering solution {} as GeoJSON", solution_id);
 
-    let feature_collection = FeatureCollection {
-        bbox: None,
-        features,
-        foreign_members: None,
-    };
+    let stored_solution = state.get_solution(&solution_id)
+        .map_err(|e| HandlerError::StateError(e))?
+        .ok_or_else(|| HandlerError::NotFound(format!("Solution {} not found", solution_id)))?;
+
+    let stored_vrp = state.get_vrp_instance(&stored_solution.vrp_id)
+        .map_err(|e| HandlerError::StateError(e))?
+        .ok_or_else(|| HandlerError::NotFound(format!("VRP instance {} not found", stored_solution.vrp_id)))?;
+
+    let geojson = crate::export::solution_to_geojson(&stored_solution.solution, &stored_vrp.instance);
+    Ok(Json(geojson))
+}
+
+// Default spacing between interpolated OSRM geometry points when the caller
+// doesn't specify `step_meters`.
+const DEFAULT_OSRM_STEP_METERS: f64 = 50.0;
+
+// Get solution rendered as an OSRM-compatible route response, so clients
+// built against the OSRM `/route` API can consume OptiQRoute output directly.
+async fn get_solution_osrm(
+    State(state): State<AppState>,
+    Path(solution_id): Path<Uuid>,
+    Query(params): Query<OsrmExportQuery>,
+) -> Result<Json<crate::osrm::OsrmResponse>, HandlerError> {
+    info!("Rendering solution {} as an OSRM route response", solution_id);
+
+    let stored_solution = state.get_solution(&solution_id)
+        .map_err(|e| HandlerError::StateError(e))?
+        .ok_or_else(|| HandlerError::NotFound(format!("Solution {} not found", solution_id)))?;
+
+    let stored_vrp = state.get_vrp_instance(&stored_solution.vrp_id)
+        .map_err(|e| HandlerError::StateError(e))?
+        .ok_or_else(|| HandlerError::NotFound(format!("VRP instance {} not found", stored_solution.vrp_id)))?;
 
-    Ok(GeoJson::FeatureCollection(feature_collection))
+    let step_meters = params.step_meters.unwrap_or(DEFAULT_OSRM_STEP_METERS);
+    let osrm_response = crate::osrm::solution_to_osrm(&stored_solution.solution, &stored_vrp.instance, step_meters);
+    Ok(Json(osrm_response))
 }
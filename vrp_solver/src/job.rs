@@ -0,0 +1,102 @@
+//! Background solve-job tracking for the asynchronous `/vrp/solve` mode
+//!
+//! A [`SolveJob`] is the shared, lock-protected state a spawned solve task
+//! writes progress into and an [`AtomicBool`] stop flag the task polls
+//! between generations, so `GET`/`DELETE /vrp/job/:job_id` can report on or
+//! cancel a solve that outlives the request that started it.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use uuid::Uuid;
+
+/// Lifecycle of a background solve job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+/// Latest known progress of a running solve job.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct JobProgress {
+    pub best_cost: Option<f64>,
+    pub elapsed_ms: f64,
+    pub generations: usize,
+}
+
+/// Shared state for one background solve: written by the spawned task as it
+/// runs, and read (or cancelled) by the job-status/cancel handlers.
+pub struct SolveJob {
+    status: RwLock<JobStatus>,
+    progress: RwLock<JobProgress>,
+    solution_id: RwLock<Option<Uuid>>,
+    error: RwLock<Option<String>>,
+    stop: AtomicBool,
+}
+
+impl SolveJob {
+    pub fn new() -> Self {
+        Self {
+            status: RwLock::new(JobStatus::Queued),
+            progress: RwLock::new(JobProgress::default()),
+            solution_id: RwLock::new(None),
+            error: RwLock::new(None),
+            stop: AtomicBool::new(false),
+        }
+    }
+
+    pub fn status(&self) -> JobStatus {
+        *self.status.read().unwrap()
+    }
+
+    pub fn set_status(&self, status: JobStatus) {
+        *self.status.write().unwrap() = status;
+    }
+
+    pub fn progress(&self) -> JobProgress {
+        self.progress.read().unwrap().clone()
+    }
+
+    pub fn set_progress(&self, progress: JobProgress) {
+        *self.progress.write().unwrap() = progress;
+    }
+
+    pub fn solution_id(&self) -> Option<Uuid> {
+        *self.solution_id.read().unwrap()
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.error.read().unwrap().clone()
+    }
+
+    /// Mark the job done and record the resulting solution.
+    pub fn finish(&self, solution_id: Uuid) {
+        *self.solution_id.write().unwrap() = Some(solution_id);
+        self.set_status(JobStatus::Done);
+    }
+
+    /// Mark the job failed and record why.
+    pub fn fail(&self, message: String) {
+        *self.error.write().unwrap() = Some(message);
+        self.set_status(JobStatus::Failed);
+    }
+
+    /// Request cancellation; the solver loop checks this between generations.
+    pub fn cancel(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    pub fn stop_flag(&self) -> &AtomicBool {
+        &self.stop
+    }
+}
+
+impl Default for SolveJob {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -6,15 +6,33 @@
 pub mod types;
 pub mod distance;
 pub mod solver;
+pub mod local_search;
+pub mod vicinity;
 pub mod validate;
+pub mod diff;
 pub mod utils;
 pub mod osm_parser;
+pub mod tsplib;
+pub mod export;
+pub mod osrm;
+pub mod routing;
+pub mod termination;
+pub mod store;
+pub mod job;
+pub mod app_state;
 
 pub use types::*;
 pub use distance::*;
 pub use solver::*;
+pub use local_search::*;
+pub use vicinity::*;
 pub use validate::*;
+pub use diff::*;
 pub use utils::*;
+pub use tsplib::*;
+pub use export::*;
+pub use osrm::*;
+pub use termination::*;
 
 /// Result type for VRP operations
 pub type VrpResult<T> = Result<T, VrpError>;
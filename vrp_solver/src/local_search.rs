@@ -0,0 +1,248 @@
+//! Post-construction local search passes for improving an already-feasible
+//! [`Solution`] without changing which customer is assigned to which vehicle.
+
+use crate::distance::{calculate_route_distance, calculate_route_duration};
+use crate::types::{Route, Solution, VrpInstance};
+
+/// A route-local improvement pass, applied to every route in a [`Solution`]
+/// independently. Implementers never move a customer between vehicles —
+/// only reorder the stops already assigned to one.
+pub trait LocalSearch {
+    fn improve(&self, solution: &mut Solution, instance: &VrpInstance);
+}
+
+fn location_index(instance: &VrpInstance, location_id: usize) -> Option<usize> {
+    instance.locations.iter().position(|loc| loc.id == location_id)
+}
+
+fn recompute_route_metrics(route: &mut Route, instance: &VrpInstance, indices: &[usize], depot_idx: usize) {
+    route.locations = indices.iter().map(|&idx| instance.locations[idx].id).collect();
+    route.total_distance = calculate_route_distance(instance, indices, depot_idx);
+    if let Some(duration) = calculate_route_duration(instance, indices, depot_idx) {
+        route.total_duration = duration;
+    }
+}
+
+fn resync_solution_totals(solution: &mut Solution) {
+    solution.total_distance = solution.routes.iter().map(|r| r.total_distance).sum();
+    solution.total_duration = solution.routes.iter().map(|r| r.total_duration).sum();
+}
+
+/// Classic 2-opt: for every pair of edge positions `(i, j)` in a route,
+/// reverse the sub-sequence between them whenever doing so shortens the
+/// route (depot implicitly at both ends), repeating full sweeps until one
+/// makes no improvement.
+#[derive(Debug, Default)]
+pub struct TwoOpt;
+
+impl TwoOpt {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn improve_route(&self, route: &mut Route, instance: &VrpInstance, depot_idx: usize) {
+        let customer_indices: Vec<usize> = route.locations.iter()
+            .filter_map(|&id| location_index(instance, id))
+            .collect();
+
+        if customer_indices.len() < 2 {
+            return;
+        }
+
+        let mut seq = Vec::with_capacity(customer_indices.len() + 2);
+        seq.push(depot_idx);
+        seq.extend(&customer_indices);
+        seq.push(depot_idx);
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            let n = seq.len();
+
+            for i in 1..n - 2 {
+                for j in (i + 1)..n - 1 {
+                    let current = instance.distance_matrix[seq[i - 1]][seq[i]]
+                        + instance.distance_matrix[seq[j]][seq[j + 1]];
+                    let swapped = instance.distance_matrix[seq[i - 1]][seq[j]]
+                        + instance.distance_matrix[seq[i]][seq[j + 1]];
+
+                    if swapped < current - f64::EPSILON {
+                        seq[i..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        let indices = seq[1..seq.len() - 1].to_vec();
+        recompute_route_metrics(route, instance, &indices, depot_idx);
+    }
+}
+
+impl LocalSearch for TwoOpt {
+    fn improve(&self, solution: &mut Solution, instance: &VrpInstance) {
+        for route in &mut solution.routes {
+            if let Some(vehicle) = instance.get_vehicle(route.vehicle_id) {
+                if let Some(depot_idx) = location_index(instance, vehicle.depot_id) {
+                    self.improve_route(route, instance, depot_idx);
+                }
+            }
+        }
+        resync_solution_totals(solution);
+    }
+}
+
+/// Or-opt: relocate contiguous segments of 1–3 customers to a cheaper
+/// position within the same route, repeating full sweeps until none
+/// improves it. Complements [`TwoOpt`], which only reorders via reversal.
+#[derive(Debug, Default)]
+pub struct OrOpt;
+
+impl OrOpt {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn improve_route(&self, route: &mut Route, instance: &VrpInstance, depot_idx: usize) {
+        let mut indices: Vec<usize> = route.locations.iter()
+            .filter_map(|&id| location_index(instance, id))
+            .collect();
+
+        if indices.len() < 2 {
+            return;
+        }
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            let n = indices.len();
+
+            'sweep: for seg_len in 1..=3usize.min(n.saturating_sub(1)) {
+                for start in 0..=(n - seg_len) {
+                    let end = start + seg_len;
+                    let segment = indices[start..end].to_vec();
+
+                    let mut remainder = indices.clone();
+                    remainder.drain(start..end);
+                    let remainder_len = remainder.len();
+
+                    let old_prev = if start == 0 { depot_idx } else { indices[start - 1] };
+                    let old_next = if end == n { depot_idx } else { indices[end] };
+                    let removal_saving = instance.distance_matrix[old_prev][segment[0]]
+                        + instance.distance_matrix[*segment.last().unwrap()][old_next]
+                        - instance.distance_matrix[old_prev][old_next];
+
+                    let mut best: Option<(usize, f64)> = None;
+                    for insert_at in 0..=remainder_len {
+                        // Reinserting at the gap it was just removed from is a no-op.
+                        if insert_at == start {
+                            continue;
+                        }
+
+                        let prev = if insert_at == 0 { depot_idx } else { remainder[insert_at - 1] };
+                        let next = if insert_at == remainder_len { depot_idx } else { remainder[insert_at] };
+
+                        let insertion_cost = instance.distance_matrix[prev][segment[0]]
+                            + instance.distance_matrix[*segment.last().unwrap()][next]
+                            - instance.distance_matrix[prev][next];
+
+                        if best.is_none() || insertion_cost < best.unwrap().1 {
+                            best = Some((insert_at, insertion_cost));
+                        }
+                    }
+
+                    if let Some((best_at, insertion_cost)) = best {
+                        if insertion_cost < removal_saving - f64::EPSILON {
+                            let mut new_indices = remainder;
+                            for (offset, loc) in segment.into_iter().enumerate() {
+                                new_indices.insert(best_at + offset, loc);
+                            }
+                            indices = new_indices;
+                            improved = true;
+                            break 'sweep;
+                        }
+                    }
+                }
+            }
+        }
+
+        recompute_route_metrics(route, instance, &indices, depot_idx);
+    }
+}
+
+impl LocalSearch for OrOpt {
+    fn improve(&self, solution: &mut Solution, instance: &VrpInstance) {
+        for route in &mut solution.routes {
+            if let Some(vehicle) = instance.get_vehicle(route.vehicle_id) {
+                if let Some(depot_idx) = location_index(instance, vehicle.depot_id) {
+                    self.improve_route(route, instance, depot_idx);
+                }
+            }
+        }
+        resync_solution_totals(solution);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::{calculate_distance_matrix, DistanceMethod};
+    use crate::types::*;
+
+    fn create_test_instance() -> VrpInstance {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(1.0, 0.0), 10.0, None, 5.0),
+            Location::new(2, "Customer 2".to_string(), Coordinate::new(2.0, 0.0), 10.0, None, 5.0),
+            Location::new(3, "Customer 3".to_string(), Coordinate::new(1.0, 1.0), 10.0, None, 5.0),
+            Location::new(4, "Customer 4".to_string(), Coordinate::new(2.0, 1.0), 10.0, None, 5.0),
+        ];
+        let vehicles = vec![Vehicle::new(0, 100.0, None, None, 0)];
+
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+        instance
+    }
+
+    #[test]
+    fn test_two_opt_untangles_crossed_route() {
+        let instance = create_test_instance();
+        // Depot(0,0) -> 1(1,0) -> 4(2,1) -> 2(2,0) -> 3(1,1) -> depot crosses
+        // itself; 2-opt should find the shorter non-crossing order.
+        let mut route = Route::new(0);
+        route.locations = vec![1, 4, 2, 3];
+        route.total_demand = 40.0;
+        route.total_distance = calculate_route_distance(&instance, &[1, 4, 2, 3], 0);
+
+        let mut solution = Solution::new();
+        solution.add_route(route);
+
+        let before = solution.routes[0].total_distance;
+        TwoOpt::new().improve(&mut solution, &instance);
+        let after = solution.routes[0].total_distance;
+
+        assert!(after <= before);
+        assert_eq!(solution.total_distance, after);
+    }
+
+    #[test]
+    fn test_or_opt_relocates_out_of_order_customer() {
+        let instance = create_test_instance();
+        // Customer 3 sits between 1 and 2 even though it's off to the side;
+        // Or-opt should relocate it to a cheaper spot in the route.
+        let mut route = Route::new(0);
+        route.locations = vec![1, 3, 2, 4];
+        route.total_demand = 40.0;
+        route.total_distance = calculate_route_distance(&instance, &[1, 3, 2, 4], 0);
+
+        let mut solution = Solution::new();
+        solution.add_route(route);
+
+        let before = solution.routes[0].total_distance;
+        OrOpt::new().improve(&mut solution, &instance);
+        let after = solution.routes[0].total_distance;
+
+        assert!(after <= before);
+        assert_eq!(solution.routes[0].locations.len(), 4);
+    }
+}
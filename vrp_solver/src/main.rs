@@ -85,9 +85,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("  GET  /health              - Health check");
     info!("  GET  /stats               - Application statistics");
     info!("  POST /osm/upload          - Upload OSM PBF file or URL");
+    info!("  POST /vrp/import-tsplib   - Import a TSPLIB/CVRPLIB problem file");
     info!("  POST /vrp/map             - Map depot/customers to OSM nodes");
     info!("  POST /vrp/generate        - Generate VRP instance");
-    info!("  POST /vrp/solve           - Solve VRP instance");
+    info!("  POST /vrp/solve           - Solve VRP instance (pass \"async\":true to run in the background)");
+    info!("  GET  /vrp/job/{{id}}        - Poll a background solve job's status/progress");
+    info!("  DELETE /vrp/job/{{id}}      - Cancel a running background solve job");
     info!("  GET  /vrp/solution/{{id}}   - Get solution details");
     info!("  GET  /vrp/solution/{{id}}/export?format=geojson - Export solution");
     info!("");
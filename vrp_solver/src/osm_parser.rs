@@ -0,0 +1,782 @@
+use osmpbfreader::{OsmPbfReader, OsmObj};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use geojson::{GeoJson, Geometry, Value, Feature, FeatureCollection};
+use serde_json::Map;
+use rstar::{RTree, RTreeObject, PointDistance, AABB};
+use async_trait::async_trait;
+use sqlx::Row;
+
+use crate::types::{Coordinate, VehicleProfile};
+
+/// Public Overpass instance used when `parse_from_overpass` isn't given an
+/// explicit `endpoint` override (e.g. a self-hosted mirror).
+pub const DEFAULT_OVERPASS_ENDPOINT: &str = "https://overpass-api.de/api/interpreter";
+
+/// Shape of an Overpass `[out:json]` response, just enough to rebuild
+/// `OsmData.nodes`/`OsmData.ways` from its flat `elements` array.
+#[derive(Debug, Deserialize)]
+struct OverpassResponse {
+    elements: Vec<OverpassElement>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OverpassElement {
+    #[serde(rename = "type")]
+    kind: String,
+    id: i64,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    nodes: Option<Vec<i64>>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsmNode {
+    pub id: i64,
+    pub lat: f64,
+    pub lon: f64,
+    pub tags: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsmWay {
+    pub id: i64,
+    pub nodes: Vec<i64>,
+    pub tags: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsmData {
+    pub nodes: HashMap<i64, OsmNode>,
+    pub ways: HashMap<i64, OsmWay>,
+}
+
+/// Saves `data` to `path`, picking the binary `bincode` encoding when `path`
+/// ends in `.bin` and pretty JSON otherwise. Large extracts parse and load
+/// far faster out of the binary form, since there's no text scanning or
+/// per-field JSON parsing to do.
+pub fn save_osm_data(data: &OsmData, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if path.ends_with(".bin") {
+        let file = File::create(path)?;
+        bincode::serialize_into(file, data)?;
+    } else {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, data)?;
+    }
+    Ok(())
+}
+
+/// Loads [`OsmData`] previously written by [`save_osm_data`], auto-detecting
+/// the binary `bincode` form from a `.bin` extension and falling back to JSON
+/// for everything else (including the historical `.osm.pbf.json` name).
+pub fn load_osm_data(path: &str) -> Result<OsmData, Box<dyn std::error::Error>> {
+    if path.ends_with(".bin") {
+        let file = File::open(path)?;
+        Ok(bincode::deserialize_from(BufReader::new(file))?)
+    } else {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+}
+
+/// A pluggable source of road-network data, so something other than a parsed
+/// `.osm.pbf` extract (e.g. a pre-built [`PostgisSource`] network) can
+/// populate the same [`OsmData`] the rest of the crate consumes.
+#[async_trait]
+pub trait GraphSource {
+    async fn load(&self) -> Result<OsmData, Box<dyn std::error::Error>>;
+}
+
+/// Loads a road network straight from PostGIS edge/node tables: `node_table`
+/// holds one row per routable vertex (an id plus a point geometry column),
+/// `edge_table` holds one row per edge referencing two of those vertex ids
+/// plus a line geometry and a precomputed cost. Each edge is loaded without
+/// a `oneway` tag, so `RoadGraph::build` routes it in both directions. Table and column
+/// names can't be bound as query parameters, so [`Self::load`] validates
+/// them against a plain alphanumeric/underscore pattern before interpolating
+/// them into SQL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostgisSource {
+    pub connection_url: String,
+    pub node_table: String,
+    pub node_geom_column: String,
+    pub edge_table: String,
+    pub geometry_column: String,
+    pub from_node_column: String,
+    pub to_node_column: String,
+    pub cost_column: String,
+}
+
+impl PostgisSource {
+    fn validate_identifiers(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let identifiers = [
+            self.node_table.as_str(),
+            self.node_geom_column.as_str(),
+            self.edge_table.as_str(),
+            self.geometry_column.as_str(),
+            self.from_node_column.as_str(),
+            self.to_node_column.as_str(),
+            self.cost_column.as_str(),
+        ];
+        for identifier in identifiers {
+            if identifier.is_empty() || !identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("Invalid PostGIS identifier: {}", identifier).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds the node nearest `(lat, lon)` with a single PostGIS KNN query
+    /// (`ORDER BY geom <-> point LIMIT 1`, index-accelerated) instead of
+    /// going through [`GraphSource::load`] and scanning every node in
+    /// memory, for callers that only need one snapped vertex (e.g. depot
+    /// resolution before a full network load).
+    pub async fn find_nearest_node(&self, lat: f64, lon: f64) -> Result<Option<(i64, f64, f64)>, Box<dyn std::error::Error>> {
+        self.validate_identifiers()?;
+
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(1)
+            .connect(&self.connection_url)
+            .await?;
+
+        let query = format!(
+            "SELECT id, ST_X({geom}) AS lon, ST_Y({geom}) AS lat FROM {table} \
+             ORDER BY {geom} <-> ST_SetSRID(ST_MakePoint($1, $2), 4326) LIMIT 1",
+            geom = self.node_geom_column,
+            table = self.node_table,
+        );
+        let row = sqlx::query(&query).bind(lon).bind(lat).fetch_optional(&pool).await?;
+
+        row.map(|row| {
+            let id: i64 = row.try_get("id")?;
+            let lon: f64 = row.try_get("lon")?;
+            let lat: f64 = row.try_get("lat")?;
+            Ok((id, lat, lon))
+        }).transpose()
+    }
+}
+
+#[async_trait]
+impl GraphSource for PostgisSource {
+    async fn load(&self) -> Result<OsmData, Box<dyn std::error::Error>> {
+        self.validate_identifiers()?;
+
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(1)
+            .connect(&self.connection_url)
+            .await?;
+
+        let mut nodes = HashMap::new();
+        let node_query = format!(
+            "SELECT id, ST_X({geom}) AS lon, ST_Y({geom}) AS lat FROM {table}",
+            geom = self.node_geom_column,
+            table = self.node_table,
+        );
+        for row in sqlx::query(&node_query).fetch_all(&pool).await? {
+            let id: i64 = row.try_get("id")?;
+            let lon: f64 = row.try_get("lon")?;
+            let lat: f64 = row.try_get("lat")?;
+            nodes.insert(id, OsmNode { id, lat, lon, tags: HashMap::new() });
+        }
+
+        // The edge table's own geometry column isn't needed beyond
+        // endpoints: `RoadGraph::build` re-derives edge length from the
+        // endpoint coordinates above, same as it does for OSM way nodes.
+        let mut ways = HashMap::new();
+        let edge_query = format!(
+            "SELECT {from_col} AS from_node, {to_col} AS to_node, {cost_col} AS cost FROM {table}",
+            from_col = self.from_node_column,
+            to_col = self.to_node_column,
+            cost_col = self.cost_column,
+            table = self.edge_table,
+        );
+        for (index, row) in sqlx::query(&edge_query).fetch_all(&pool).await?.into_iter().enumerate() {
+            let from_node: i64 = row.try_get("from_node")?;
+            let to_node: i64 = row.try_get("to_node")?;
+            let cost: f64 = row.try_get("cost")?;
+
+            let mut tags = HashMap::new();
+            // `RoadGraph::build` only routes ways tagged `highway`; PostGIS
+            // edges have no OSM road classification, so mark them generically
+            // routable and carry the table's own cost alongside for callers
+            // that want it instead of the haversine-derived edge weight.
+            tags.insert("highway".to_string(), "unclassified".to_string());
+            tags.insert("postgis_cost".to_string(), cost.to_string());
+
+            // Synthesize a negative id so it can't collide with a real OSM
+            // way id if this graph is ever merged with a parsed PBF extract.
+            let way_id = -(index as i64 + 1);
+            ways.insert(way_id, OsmWay { id: way_id, nodes: vec![from_node, to_node], tags });
+        }
+
+        Ok(OsmData { nodes, ways })
+    }
+}
+
+pub struct OsmParser {
+    pub data: OsmData,
+    /// Built once parsing finishes (and rebuilt after filtering), so repeated
+    /// `find_nearest_node`/`snap_locations` calls don't each rescan `data.nodes`.
+    spatial_index: Option<NodeSpatialIndex>,
+}
+
+impl OsmParser {
+    pub fn new() -> Self {
+        Self {
+            data: OsmData {
+                nodes: HashMap::new(),
+                ways: HashMap::new(),
+            },
+            spatial_index: None,
+        }
+    }
+
+    /// Wraps an already-parsed [`OsmData`] snapshot (e.g. one loaded back out
+    /// of storage) in a parser with its spatial index built immediately,
+    /// rather than only after `parse_pbf_file`/`filter_roads_only` run.
+    pub fn from_data(data: OsmData) -> Self {
+        let mut parser = Self { data, spatial_index: None };
+        parser.rebuild_spatial_index();
+        parser
+    }
+
+    fn rebuild_spatial_index(&mut self) {
+        self.spatial_index = Some(NodeSpatialIndex::build(&self.data));
+    }
+
+    pub fn parse_pbf_file(&mut self, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        println!("Opening PBF file: {}", file_path);
+        let file = File::open(file_path)?;
+        let buf_reader = BufReader::new(file);
+        let mut pbf_reader = OsmPbfReader::new(buf_reader);
+
+        let mut node_count = 0;
+        let mut way_count = 0;
+
+        for obj in pbf_reader.iter().map(|o| o.unwrap()) {
+            match obj {
+                OsmObj::Node(node) => {
+                    node_count += 1;
+                    if node_count % 100000 == 0 {
+                        println!("Processed {} nodes", node_count);
+                    }
+
+                    let osm_node = OsmNode {
+                        id: node.id.0,
+                        lat: node.lat(),
+                        lon: node.lon(),
+                        tags: node.tags.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+                    };
+                    self.data.nodes.insert(node.id.0, osm_node);
+                }
+                OsmObj::Way(way) => {
+                    way_count += 1;
+                    if way_count % 10000 == 0 {
+                        println!("Processed {} ways", way_count);
+                    }
+
+                    let osm_way = OsmWay {
+                        id: way.id.0,
+                        nodes: way.nodes.iter().map(|n| n.0).collect(),
+                        tags: way.tags.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+                    };
+                    self.data.ways.insert(way.id.0, osm_way);
+                }
+                OsmObj::Relation(_) => {
+                    // Skip relations for now, focus on nodes and ways
+                    continue;
+                }
+            }
+        }
+
+        println!("Finished parsing PBF file:");
+        println!("  - Nodes: {}", node_count);
+        println!("  - Ways: {}", way_count);
+
+        self.rebuild_spatial_index();
+
+        Ok(())
+    }
+
+    /// Fetch roads within `bbox` (south, west, north, east) straight from an
+    /// Overpass API instance instead of requiring a pre-downloaded `.osm.pbf`.
+    /// Issues `way["<filter>"](south,west,north,east);` for each of `filters`
+    /// (e.g. `&["highway"]`), then `filter_roads_only`s the result the same
+    /// way a parsed PBF extract would be.
+    pub async fn parse_from_overpass(
+        &mut self,
+        bbox: (f64, f64, f64, f64),
+        filters: &[&str],
+        endpoint: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (south, west, north, east) = bbox;
+        let way_clauses: String = filters
+            .iter()
+            .map(|filter| format!("way[\"{}\"]({},{},{},{});", filter, south, west, north, east))
+            .collect();
+        let query = format!("[out:json];({}); out body; >; out skel qt;", way_clauses);
+
+        println!("Querying Overpass for bbox ({}, {}, {}, {})", south, west, north, east);
+
+        let client = reqwest::Client::new();
+        let response = client
+            .post(endpoint.unwrap_or(DEFAULT_OVERPASS_ENDPOINT))
+            .form(&[("data", query.as_str())])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let overpass: OverpassResponse = response.json().await?;
+
+        for element in overpass.elements {
+            match element.kind.as_str() {
+                "node" => {
+                    if let (Some(lat), Some(lon)) = (element.lat, element.lon) {
+                        self.data.nodes.insert(
+                            element.id,
+                            OsmNode { id: element.id, lat, lon, tags: element.tags },
+                        );
+                    }
+                }
+                "way" => {
+                    self.data.ways.insert(
+                        element.id,
+                        OsmWay { id: element.id, nodes: element.nodes.unwrap_or_default(), tags: element.tags },
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        println!(
+            "Fetched from Overpass: {} nodes, {} ways",
+            self.data.nodes.len(),
+            self.data.ways.len()
+        );
+
+        self.filter_roads_only();
+
+        Ok(())
+    }
+
+    pub fn export_to_json(&self, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        println!("Exporting to JSON: {}", file_path);
+        let json_str = serde_json::to_string_pretty(&self.data)?;
+        std::fs::write(file_path, json_str)?;
+        println!("JSON export completed");
+        Ok(())
+    }
+
+    /// Exports to the compact `bincode` form instead of JSON, for large
+    /// extracts where the JSON parse would otherwise dominate load time.
+    /// Unlike [`save_osm_data`], always writes binary regardless of
+    /// `file_path`'s extension, since callers ask for this format explicitly.
+    pub fn export_to_binary(&self, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        println!("Exporting to binary: {}", file_path);
+        let file = File::create(file_path)?;
+        bincode::serialize_into(file, &self.data)?;
+        println!("Binary export completed");
+        Ok(())
+    }
+
+    pub fn export_to_geojson(&self, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        println!("Exporting to GeoJSON: {}", file_path);
+        
+        let mut features = Vec::new();
+
+        // Add nodes as Point features
+        for (_, node) in &self.data.nodes {
+            let geometry = Geometry::new(Value::Point(vec![node.lon, node.lat]));
+            
+            let mut properties = Map::new();
+            properties.insert("id".to_string(), serde_json::Value::Number(node.id.into()));
+            properties.insert("type".to_string(), serde_json::Value::String("node".to_string()));
+            
+            // Add all tags as properties
+            for (key, value) in &node.tags {
+                properties.insert(key.clone(), serde_json::Value::String(value.clone()));
+            }
+
+            let feature = Feature {
+                bbox: None,
+                geometry: Some(geometry),
+                id: None,
+                properties: Some(properties),
+                foreign_members: None,
+            };
+
+            features.push(feature);
+        }
+
+        // Add ways as LineString features
+        for (_, way) in &self.data.ways {
+            let mut coordinates = Vec::new();
+            let mut valid_way = true;
+
+            // Get coordinates for all nodes in the way
+            for node_id in &way.nodes {
+                if let Some(node) = self.data.nodes.get(node_id) {
+                    coordinates.push(vec![node.lon, node.lat]);
+                } else {
+                    // If we can't find a node, skip this way
+                    valid_way = false;
+                    break;
+                }
+            }
+
+            if valid_way && coordinates.len() >= 2 {
+                let geometry = Geometry::new(Value::LineString(coordinates));
+                
+                let mut properties = Map::new();
+                properties.insert("id".to_string(), serde_json::Value::Number(way.id.into()));
+                properties.insert("type".to_string(), serde_json::Value::String("way".to_string()));
+                
+                // Add all tags as properties
+                for (key, value) in &way.tags {
+                    properties.insert(key.clone(), serde_json::Value::String(value.clone()));
+                }
+
+                let feature = Feature {
+                    bbox: None,
+                    geometry: Some(geometry),
+                    id: None,
+                    properties: Some(properties),
+                    foreign_members: None,
+                };
+
+                features.push(feature);
+            }
+        }
+
+        let feature_collection = FeatureCollection {
+            bbox: None,
+            features,
+            foreign_members: None,
+        };
+
+        let geojson = GeoJson::FeatureCollection(feature_collection);
+        let geojson_str = serde_json::to_string_pretty(&geojson)?;
+        std::fs::write(file_path, geojson_str)?;
+        
+        println!("GeoJSON export completed");
+        Ok(())
+    }
+
+    pub fn filter_roads_only(&mut self) {
+        // Keep only ways that are roads/highways
+        self.data.ways.retain(|_, way| {
+            way.tags.contains_key("highway")
+        });
+
+        // Keep only nodes that are referenced by remaining ways
+        let mut referenced_nodes = std::collections::HashSet::new();
+        for way in self.data.ways.values() {
+            for node_id in &way.nodes {
+                referenced_nodes.insert(*node_id);
+            }
+        }
+
+        self.data.nodes.retain(|id, _| referenced_nodes.contains(id));
+
+        println!("Filtered to roads only:");
+        println!("  - Nodes: {}", self.data.nodes.len());
+        println!("  - Ways: {}", self.data.ways.len());
+
+        self.rebuild_spatial_index();
+    }
+
+    /// Like [`filter_roads_only`](Self::filter_roads_only), but also drops
+    /// way types unsuitable for `profile`: motorways aren't walkable or
+    /// cyclable, and footways aren't drivable. Use this instead when a
+    /// graph is being built for a specific vehicle profile.
+    pub fn filter_roads_for_profile(&mut self, profile: VehicleProfile) {
+        self.filter_roads_only();
+
+        self.data.ways.retain(|_, way| {
+            let highway = way.tags.get("highway").map(String::as_str).unwrap_or("");
+            match profile {
+                VehicleProfile::Driving => !matches!(highway, "footway" | "path" | "pedestrian" | "steps"),
+                VehicleProfile::Cycling | VehicleProfile::Walking => {
+                    !matches!(highway, "motorway" | "motorway_link")
+                }
+            }
+        });
+
+        let mut referenced_nodes = std::collections::HashSet::new();
+        for way in self.data.ways.values() {
+            for node_id in &way.nodes {
+                referenced_nodes.insert(*node_id);
+            }
+        }
+        self.data.nodes.retain(|id, _| referenced_nodes.contains(id));
+
+        println!("Filtered roads for {:?} profile:", profile);
+        println!("  - Nodes: {}", self.data.nodes.len());
+        println!("  - Ways: {}", self.data.ways.len());
+
+        self.rebuild_spatial_index();
+    }
+
+    pub fn get_node_coordinates(&self, node_id: i64) -> Option<(f64, f64)> {
+        self.data.nodes.get(&node_id).map(|node| (node.lat, node.lon))
+    }
+
+    /// Nearest node to `(lat, lon)`, served by the R-tree built after parsing
+    /// (and rebuilt after filtering) when available, falling back to an O(n)
+    /// scan over `data.nodes` otherwise.
+    pub fn find_nearest_node(&self, lat: f64, lon: f64) -> Option<(i64, f64)> {
+        if let Some(index) = &self.spatial_index {
+            return index.nearest(lat, lon);
+        }
+
+        let mut nearest_node = None;
+        let mut min_distance = f64::MAX;
+
+        for (id, node) in &self.data.nodes {
+            let distance = haversine_distance(lat, lon, node.lat, node.lon);
+            if distance < min_distance {
+                min_distance = distance;
+                nearest_node = Some((*id, distance));
+            }
+        }
+
+        nearest_node
+    }
+
+    /// Snaps each coordinate to its nearest node, reusing the same R-tree
+    /// built after parsing instead of one `find_nearest_node` scan per
+    /// location. `None` for a location only when `data.nodes` is empty.
+    pub fn snap_locations(&self, coordinates: &[Coordinate]) -> Vec<Option<(i64, f64)>> {
+        coordinates
+            .iter()
+            .map(|coordinate| self.find_nearest_node(coordinate.lat, coordinate.lon))
+            .collect()
+    }
+
+    /// Build an R-tree over every node for fast repeated nearest-node
+    /// lookups (e.g. snapping a depot plus many customers in `map_locations`),
+    /// instead of each call doing its own O(n) scan via `find_nearest_node`.
+    pub fn build_spatial_index(&self) -> NodeSpatialIndex {
+        NodeSpatialIndex::build(&self.data)
+    }
+}
+
+fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let r = 6371000.0; // Earth's radius in meters
+    let phi1 = lat1.to_radians();
+    let phi2 = lat2.to_radians();
+    let delta_phi = (lat2 - lat1).to_radians();
+    let delta_lambda = (lon2 - lon1).to_radians();
+
+    let a = (delta_phi / 2.0).sin().powi(2) +
+            phi1.cos() * phi2.cos() *
+            (delta_lambda / 2.0).sin().powi(2);
+    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+
+    r * c
+}
+
+/// A node position indexed in an R-tree, keyed by (lon, lat) since `rstar`
+/// expects `[f64; N]` points and GeoJSON-style (lon, lat) ordering matches
+/// the rest of this module's coordinate handling.
+#[derive(Debug, Clone, Copy)]
+struct IndexedNode {
+    id: i64,
+    lat: f64,
+    lon: f64,
+}
+
+impl RTreeObject for IndexedNode {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_point([self.lon, self.lat])
+    }
+}
+
+impl PointDistance for IndexedNode {
+    /// Squared *haversine* distance in meters, not raw degree distance, so
+    /// ranking stays correct across latitudes (a degree of longitude shrinks
+    /// towards the poles, which a plain Euclidean (lon, lat) metric ignores).
+    /// This is always a looser lower bound than the tree's own Euclidean
+    /// envelope pruning, which only costs some extra candidate visits, not
+    /// correctness.
+    fn distance_2(&self, point: &[f64; 2]) -> f64 {
+        let distance = haversine_distance(point[1], point[0], self.lat, self.lon);
+        distance * distance
+    }
+}
+
+/// An R-tree over a graph's nodes, built by [`OsmParser::build_spatial_index`].
+/// Reports true haversine distance (not the tree's planar metric) so results
+/// match `OsmParser::find_nearest_node` exactly.
+pub struct NodeSpatialIndex {
+    tree: RTree<IndexedNode>,
+}
+
+impl NodeSpatialIndex {
+    /// Builds an index straight from a parsed [`OsmData`] snapshot, for
+    /// callers (e.g. the instance generator) that only have the data rather
+    /// than a live [`OsmParser`].
+    pub fn build(osm_data: &OsmData) -> Self {
+        let points = osm_data.nodes.values()
+            .map(|node| IndexedNode { id: node.id, lat: node.lat, lon: node.lon })
+            .collect();
+        Self { tree: RTree::bulk_load(points) }
+    }
+
+    pub fn nearest(&self, lat: f64, lon: f64) -> Option<(i64, f64)> {
+        self.tree.nearest_neighbor(&[lon, lat]).map(|node| {
+            (node.id, haversine_distance(lat, lon, node.lat, node.lon))
+        })
+    }
+
+    /// The `k` nearest nodes to `(lat, lon)`, sorted by ascending true
+    /// haversine distance. `nearest_neighbor_iter` already yields candidates
+    /// in increasing order of [`IndexedNode`]'s haversine-based `distance_2`,
+    /// so this just takes the first `k`.
+    pub fn k_nearest(&self, lat: f64, lon: f64, k: usize) -> Vec<(i64, f64)> {
+        self.tree
+            .nearest_neighbor_iter(&[lon, lat])
+            .take(k)
+            .map(|node| (node.id, haversine_distance(lat, lon, node.lat, node.lon)))
+            .collect()
+    }
+
+    /// All nodes within `radius_m` meters of `(lat, lon)`, sorted by
+    /// ascending true haversine distance. Since [`IndexedNode`]'s
+    /// `distance_2` is already squared haversine meters, `radius_m` squared
+    /// is passed straight through as the tree's search radius.
+    pub fn within_radius(&self, lat: f64, lon: f64, radius_m: f64) -> Vec<(i64, f64)> {
+        let mut results: Vec<(i64, f64)> = self.tree
+            .locate_within_distance([lon, lat], radius_m * radius_m)
+            .map(|node| (node.id, haversine_distance(lat, lon, node.lat, node.lon)))
+            .collect();
+        results.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        results
+    }
+}
+
+/// One named place [`PlaceNameIndex`] can resolve a query to: a node (or a
+/// way, represented by the centroid of its member nodes) tagged `name` or an
+/// `addr:*` address tag.
+#[derive(Debug, Clone)]
+pub struct PlaceMatch {
+    pub node_id: i64,
+    pub lat: f64,
+    pub lon: f64,
+    pub name: String,
+}
+
+/// Indexes OSM node/way `name` and `addr:*` tags so a place can be resolved
+/// by name (e.g. `--depot-name "Main Warehouse"`) instead of by coordinate. Built
+/// once per [`OsmData`] snapshot and reused across lookups, same as
+/// [`NodeSpatialIndex`].
+pub struct PlaceNameIndex {
+    by_name: HashMap<String, Vec<PlaceMatch>>,
+}
+
+impl PlaceNameIndex {
+    pub fn build(osm_data: &OsmData) -> Self {
+        let mut by_name: HashMap<String, Vec<PlaceMatch>> = HashMap::new();
+
+        let mut index_name = |name: &str, node_id: i64, lat: f64, lon: f64| {
+            by_name.entry(name.to_lowercase()).or_default().push(PlaceMatch {
+                node_id,
+                lat,
+                lon,
+                name: name.to_string(),
+            });
+        };
+
+        for node in osm_data.nodes.values() {
+            for (key, value) in &node.tags {
+                if key == "name" || key.starts_with("addr:") {
+                    index_name(value, node.id, node.lat, node.lon);
+                }
+            }
+        }
+
+        // A way has no coordinate of its own; represent it by the centroid
+        // of whichever of its member nodes are present in `osm_data.nodes`,
+        // and snap that centroid to the nearest of those same nodes so the
+        // match is still a real, routable node id.
+        for way in osm_data.ways.values() {
+            let named_tag = way.tags.iter().find(|(key, _)| key.as_str() == "name" || key.starts_with("addr:"));
+            let Some((_, name)) = named_tag else { continue };
+
+            let member_nodes: Vec<&OsmNode> = way.nodes.iter().filter_map(|id| osm_data.nodes.get(id)).collect();
+            if member_nodes.is_empty() {
+                continue;
+            }
+
+            let centroid_lat = member_nodes.iter().map(|n| n.lat).sum::<f64>() / member_nodes.len() as f64;
+            let centroid_lon = member_nodes.iter().map(|n| n.lon).sum::<f64>() / member_nodes.len() as f64;
+            let nearest = member_nodes.iter()
+                .min_by(|a, b| {
+                    let da = haversine_distance(centroid_lat, centroid_lon, a.lat, a.lon);
+                    let db = haversine_distance(centroid_lat, centroid_lon, b.lat, b.lon);
+                    da.partial_cmp(&db).unwrap()
+                })
+                .unwrap();
+
+            index_name(name, nearest.id, nearest.lat, nearest.lon);
+        }
+
+        Self { by_name }
+    }
+
+    /// Resolves `query` to a single place: first by exact (case-insensitive)
+    /// name match, then by substring match if nothing matched exactly.
+    /// Errors if nothing matches, and errors listing the candidates if more
+    /// than one distinct node matches and the caller needs to disambiguate
+    /// (e.g. by passing a more specific query).
+    pub fn resolve(&self, query: &str) -> Result<PlaceMatch, Box<dyn std::error::Error>> {
+        let query_lc = query.to_lowercase();
+
+        if let Some(matches) = self.by_name.get(&query_lc) {
+            return Self::disambiguate(query, matches);
+        }
+
+        let substring_matches: Vec<&PlaceMatch> = self.by_name.iter()
+            .filter(|(name, _)| name.contains(&query_lc))
+            .flat_map(|(_, matches)| matches.iter())
+            .collect();
+
+        if substring_matches.is_empty() {
+            return Err(format!("No OSM place found matching \"{}\"", query).into());
+        }
+
+        let owned: Vec<PlaceMatch> = substring_matches.into_iter().cloned().collect();
+        Self::disambiguate(query, &owned)
+    }
+
+    /// A single candidate resolves outright; multiple candidates for
+    /// distinct nodes are reported as an error naming each one, so the
+    /// caller can retry with a more specific query instead of silently
+    /// picking one.
+    fn disambiguate(query: &str, matches: &[PlaceMatch]) -> Result<PlaceMatch, Box<dyn std::error::Error>> {
+        let mut distinct: Vec<&PlaceMatch> = Vec::new();
+        for candidate in matches {
+            if !distinct.iter().any(|m| m.node_id == candidate.node_id) {
+                distinct.push(candidate);
+            }
+        }
+
+        match distinct.as_slice() {
+            [] => Err(format!("No OSM place found matching \"{}\"", query).into()),
+            [only] => Ok((*only).clone()),
+            multiple => {
+                let options = multiple.iter()
+                    .map(|m| format!("\"{}\" (node {}, {:.5},{:.5})", m.name, m.node_id, m.lat, m.lon))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                Err(format!("\"{}\" is ambiguous, matches: {}", query, options).into())
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! OSRM-compatible route response serialization
+//!
+//! Lets clients built against the OSRM `/route` response shape consume
+//! OptiQRoute solutions directly: a `routes` array with `distance`,
+//! `duration`, a `geometry`, and per-leg breakdowns between consecutive stops.
+
+use crate::distance::haversine_distance;
+use crate::types::{Coordinate, Solution, VrpInstance};
+use serde::Serialize;
+
+/// Earth radius (meters) used for great-circle interpolation.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+#[derive(Debug, Serialize)]
+pub struct OsrmResponse {
+    pub code: String,
+    pub routes: Vec<OsrmRoute>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OsrmRoute {
+    pub distance: f64,
+    pub duration: f64,
+    pub geometry: OsrmGeometry,
+    pub legs: Vec<OsrmLeg>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum OsrmGeometry {
+    LineString { coordinates: Vec<[f64; 2]> },
+}
+
+#[derive(Debug, Serialize)]
+pub struct OsrmLeg {
+    pub distance: f64,
+    pub duration: f64,
+}
+
+/// A fully OSRM-spec-shaped route response: `geometry` is an encoded
+/// polyline (precision 5, as real OSRM servers default to) rather than the
+/// raw-coordinate [`OsrmGeometry::LineString`] used by [`solution_to_osrm`],
+/// and every stop is listed in `waypoints`. Served from `export_solution`'s
+/// `?format=osrm` so OSRM-consuming frontends need no server-side translation.
+#[derive(Debug, Serialize)]
+pub struct OsrmExportResponse {
+    pub code: String,
+    pub routes: Vec<OsrmExportRoute>,
+    pub waypoints: Vec<OsrmWaypoint>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OsrmExportRoute {
+    pub distance: f64,
+    pub duration: f64,
+    pub geometry: String,
+    pub legs: Vec<OsrmLeg>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OsrmWaypoint {
+    pub name: String,
+    pub location: [f64; 2],
+}
+
+/// Interpolate points along the great-circle between `start` and `end` at
+/// roughly `step_meters` spacing, always keeping the exact start and end
+/// coordinates so consumers render a smooth (not jagged) line.
+pub fn interpolate_great_circle(start: Coordinate, end: Coordinate, step_meters: f64) -> Vec<Coordinate> {
+    let total_distance = haversine_distance(start, end);
+    if step_meters <= 0.0 || total_distance <= step_meters {
+        return vec![start, end];
+    }
+
+    let num_segments = (total_distance / step_meters).ceil() as usize;
+    let angular_distance = total_distance / EARTH_RADIUS_M;
+
+    let lat1 = start.lat.to_radians();
+    let lon1 = start.lon.to_radians();
+    let lat2 = end.lat.to_radians();
+    let lon2 = end.lon.to_radians();
+    let sin_d = angular_distance.sin();
+
+    let mut points = Vec::with_capacity(num_segments + 1);
+    points.push(start);
+
+    for i in 1..num_segments {
+        let f = i as f64 / num_segments as f64;
+        let a = ((1.0 - f) * angular_distance).sin() / sin_d;
+        let b = (f * angular_distance).sin() / sin_d;
+
+        let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+        let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+        let z = a * lat1.sin() + b * lat2.sin();
+
+        let lat = z.atan2((x * x + y * y).sqrt());
+        let lon = y.atan2(x);
+        points.push(Coordinate::new(lat.to_degrees(), lon.to_degrees()));
+    }
+
+    points.push(end);
+    points
+}
+
+/// Render a solved [`Solution`] as an OSRM-style route response, segmenting
+/// every depot-stop-depot hop into interpolated points so the geometry
+/// renders smoothly even though OptiQRoute only knows straight-line edges.
+pub fn solution_to_osrm(solution: &Solution, instance: &VrpInstance, step_meters: f64) -> OsrmResponse {
+    let routes = solution
+        .routes
+        .iter()
+        .map(|route| route_to_osrm(route, instance, step_meters))
+        .collect();
+
+    OsrmResponse {
+        code: "Ok".to_string(),
+        routes,
+    }
+}
+
+fn route_to_osrm(route: &crate::types::Route, instance: &VrpInstance, step_meters: f64) -> OsrmRoute {
+    let depot_id = instance.get_vehicle(route.vehicle_id).map(|v| v.depot_id);
+
+    let mut stop_ids = Vec::with_capacity(route.locations.len() + 2);
+    stop_ids.extend(depot_id);
+    stop_ids.extend(route.locations.iter().copied());
+    stop_ids.extend(depot_id);
+
+    let mut legs = Vec::new();
+    let mut coordinates: Vec<[f64; 2]> = Vec::new();
+
+    for pair in stop_ids.windows(2) {
+        let (Some(from), Some(to)) = (instance.get_location(pair[0]), instance.get_location(pair[1])) else {
+            continue;
+        };
+
+        let distance = instance.get_distance(pair[0], pair[1]);
+        let duration = instance
+            .time_matrix
+            .as_ref()
+            .map(|m| m[pair[0]][pair[1]])
+            .unwrap_or(0.0);
+        legs.push(OsrmLeg { distance, duration });
+
+        let segment = interpolate_great_circle(from.coordinate, to.coordinate, step_meters);
+        // Drop the first point of every segment after the first so shared
+        // endpoints between consecutive legs aren't duplicated.
+        let start = if coordinates.is_empty() { 0 } else { 1 };
+        coordinates.extend(segment[start..].iter().map(|c| [c.lon, c.lat]));
+    }
+
+    OsrmRoute {
+        distance: route.total_distance,
+        duration: route.total_duration,
+        geometry: OsrmGeometry::LineString { coordinates },
+        legs,
+    }
+}
+
+/// Render a solved [`Solution`] as a spec-shaped OSRM response: one
+/// `OsrmExportRoute` per vehicle with an encoded polyline geometry (precision
+/// 5, OSRM's default), plus a `waypoints` entry for every location in the
+/// instance, in the instance's own location order. Geometry follows
+/// straight depot-stop-depot hops; use
+/// [`solution_to_osrm_export_with_road_geometry`] to follow real roads.
+pub fn solution_to_osrm_export(solution: &Solution, instance: &VrpInstance) -> OsrmExportResponse {
+    build_osrm_export(solution, instance, None, 5)
+}
+
+/// Like [`solution_to_osrm_export`], but `road_geometry[route_idx]` (the
+/// node-by-node path along `routing::RoadGraph` between each route's snapped
+/// stops, e.g. from the handler's cached road geometry) replaces the
+/// straight depot-stop-depot hops, and `precision` sets the encoded
+/// polyline's decimal digits (OSRM servers default to 5; some clients expect
+/// 6 for extra precision). A missing or too-short entry falls back to the
+/// straight-line geometry for that route.
+pub fn solution_to_osrm_export_with_road_geometry(
+    solution: &Solution,
+    instance: &VrpInstance,
+    road_geometry: Option<&[Vec<[f64; 2]>]>,
+    precision: u32,
+) -> OsrmExportResponse {
+    build_osrm_export(solution, instance, road_geometry, precision)
+}
+
+fn build_osrm_export(
+    solution: &Solution,
+    instance: &VrpInstance,
+    road_geometry: Option<&[Vec<[f64; 2]>]>,
+    precision: u32,
+) -> OsrmExportResponse {
+    let routes = solution
+        .routes
+        .iter()
+        .enumerate()
+        .map(|(route_idx, route)| {
+            let road_coords = road_geometry
+                .and_then(|geometry| geometry.get(route_idx))
+                .filter(|coords| coords.len() >= 2);
+            route_to_osrm_export(route, instance, road_coords, precision)
+        })
+        .collect();
+
+    let waypoints = instance
+        .locations
+        .iter()
+        .map(|location| OsrmWaypoint {
+            name: location.name.clone(),
+            location: [location.coordinate.lon, location.coordinate.lat],
+        })
+        .collect();
+
+    OsrmExportResponse {
+        code: "Ok".to_string(),
+        routes,
+        waypoints,
+    }
+}
+
+fn route_to_osrm_export(
+    route: &crate::types::Route,
+    instance: &VrpInstance,
+    road_coords: Option<&Vec<[f64; 2]>>,
+    precision: u32,
+) -> OsrmExportRoute {
+    let depot_id = instance.get_vehicle(route.vehicle_id).map(|v| v.depot_id);
+
+    let mut stop_ids = Vec::with_capacity(route.locations.len() + 2);
+    stop_ids.extend(depot_id);
+    stop_ids.extend(route.locations.iter().copied());
+    stop_ids.extend(depot_id);
+
+    let mut legs = Vec::new();
+    let mut straight_coordinates: Vec<[f64; 2]> = Vec::new();
+
+    for pair in stop_ids.windows(2) {
+        let (Some(from), Some(to)) = (instance.get_location(pair[0]), instance.get_location(pair[1])) else {
+            continue;
+        };
+
+        let distance = instance.get_distance(pair[0], pair[1]);
+        let duration = instance
+            .time_matrix
+            .as_ref()
+            .map(|m| m[pair[0]][pair[1]])
+            .unwrap_or(0.0);
+        legs.push(OsrmLeg { distance, duration });
+
+        if straight_coordinates.is_empty() {
+            straight_coordinates.push([from.coordinate.lon, from.coordinate.lat]);
+        }
+        straight_coordinates.push([to.coordinate.lon, to.coordinate.lat]);
+    }
+
+    let coordinates = road_coords.cloned().unwrap_or(straight_coordinates);
+
+    OsrmExportRoute {
+        distance: route.total_distance,
+        duration: route.total_duration,
+        geometry: encode_polyline(&coordinates, precision),
+        legs,
+    }
+}
+
+/// Encode `[lon, lat]` points as a Google/OSRM polyline string at `precision`
+/// decimal digits (OSRM servers default to precision 5).
+pub fn encode_polyline(coordinates: &[[f64; 2]], precision: u32) -> String {
+    let factor = 10f64.powi(precision as i32);
+    let mut output = String::new();
+    let mut prev_lat = 0i64;
+    let mut prev_lon = 0i64;
+
+    for point in coordinates {
+        let lat = (point[1] * factor).round() as i64;
+        let lon = (point[0] * factor).round() as i64;
+
+        encode_polyline_value(lat - prev_lat, &mut output);
+        encode_polyline_value(lon - prev_lon, &mut output);
+
+        prev_lat = lat;
+        prev_lon = lon;
+    }
+
+    output
+}
+
+fn encode_polyline_value(value: i64, output: &mut String) {
+    let mut value = if value < 0 { !(value << 1) } else { value << 1 };
+    while value >= 0x20 {
+        output.push((((value & 0x1f) | 0x20) as u8 + 63) as char);
+        value >>= 5;
+    }
+    output.push((value as u8 + 63) as char);
+}
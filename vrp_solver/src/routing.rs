@@ -0,0 +1,338 @@
+//! Road-network routing over a parsed OSM graph
+//!
+//! Builds a weighted adjacency graph from [`OsmData`] ways and runs
+//! many-to-many Dijkstra over it so VRP locations can be routed along real
+//! streets instead of straight lines.
+
+use crate::distance::haversine_distance;
+use crate::osm_parser::OsmData;
+use crate::types::{Coordinate, VrpInstance};
+use crate::{VrpError, VrpResult};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Which edge weight Dijkstra should accumulate: road distance (meters) or
+/// travel time (seconds, from the edge's inferred/tagged speed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    Distance,
+    Time,
+}
+
+/// A weighted adjacency graph built from OSM ways, keyed by OSM node id.
+/// Each edge carries both its distance (meters) and travel time (seconds),
+/// so the same graph serves both a distance matrix and a time matrix.
+#[derive(Debug, Clone)]
+pub struct RoadGraph {
+    adjacency: HashMap<i64, Vec<(i64, f64, f64)>>,
+}
+
+impl RoadGraph {
+    /// Build a routable graph from parsed OSM data, respecting `oneway`
+    /// (`yes`/`true`/`1` forward-only, `-1` reverse-only, anything else
+    /// bidirectional) and `maxspeed` tags. Edge distance is the haversine
+    /// length (meters) of each node-to-node segment; edge time is that
+    /// distance divided by the tagged `maxspeed`, falling back to a speed
+    /// inferred from the way's `highway` value (see [`highway_speed_kmh`]).
+    pub fn build(osm_data: &OsmData) -> Self {
+        let mut adjacency: HashMap<i64, Vec<(i64, f64, f64)>> = HashMap::new();
+
+        for way in osm_data.ways.values() {
+            let Some(highway) = way.tags.get("highway") else {
+                continue;
+            };
+            let (forward, backward) = match way.tags.get("oneway").map(|v| v.as_str()) {
+                Some("-1") => (false, true),
+                Some("yes") | Some("true") | Some("1") => (true, false),
+                _ => (true, true),
+            };
+            let speed_kmh = way
+                .tags
+                .get("maxspeed")
+                .and_then(|v| parse_maxspeed_kmh(v))
+                .unwrap_or_else(|| highway_speed_kmh(highway));
+            let speed_ms = speed_kmh * 1000.0 / 3600.0;
+
+            for pair in way.nodes.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                let (Some(node_a), Some(node_b)) = (osm_data.nodes.get(&a), osm_data.nodes.get(&b)) else {
+                    continue;
+                };
+                let distance = haversine_distance(
+                    Coordinate::new(node_a.lat, node_a.lon),
+                    Coordinate::new(node_b.lat, node_b.lon),
+                );
+                let time = distance / speed_ms;
+
+                if forward {
+                    adjacency.entry(a).or_default().push((b, distance, time));
+                }
+                if backward {
+                    adjacency.entry(b).or_default().push((a, distance, time));
+                }
+            }
+        }
+
+        Self { adjacency }
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn contains_node(&self, node_id: i64) -> bool {
+        self.adjacency.contains_key(&node_id)
+    }
+
+    /// Dijkstra shortest paths from `source` to every reachable node under
+    /// `metric`, also returning the predecessor map needed to reconstruct a path.
+    fn shortest_paths_from(&self, source: i64, metric: Metric) -> (HashMap<i64, f64>, HashMap<i64, i64>) {
+        let mut distances: HashMap<i64, f64> = HashMap::new();
+        let mut predecessors: HashMap<i64, i64> = HashMap::new();
+        let mut heap: BinaryHeap<HeapEntry> = BinaryHeap::new();
+
+        distances.insert(source, 0.0);
+        heap.push(HeapEntry { node: source, cost: 0.0 });
+
+        while let Some(HeapEntry { node, cost }) = heap.pop() {
+            if cost > *distances.get(&node).unwrap_or(&f64::INFINITY) {
+                continue;
+            }
+            if let Some(neighbors) = self.adjacency.get(&node) {
+                for &(next, distance, time) in neighbors {
+                    let weight = match metric {
+                        Metric::Distance => distance,
+                        Metric::Time => time,
+                    };
+                    let next_cost = cost + weight;
+                    if next_cost < *distances.get(&next).unwrap_or(&f64::INFINITY) {
+                        distances.insert(next, next_cost);
+                        predecessors.insert(next, node);
+                        heap.push(HeapEntry { node: next, cost: next_cost });
+                    }
+                }
+            }
+        }
+
+        (distances, predecessors)
+    }
+
+    /// Reconstruct the node path from `source` to `target` using a
+    /// predecessor map produced by [`Self::shortest_paths_from`].
+    fn reconstruct_path(predecessors: &HashMap<i64, i64>, source: i64, target: i64) -> Vec<i64> {
+        if source == target {
+            return vec![source];
+        }
+        let mut path = vec![target];
+        let mut current = target;
+        while let Some(&prev) = predecessors.get(&current) {
+            path.push(prev);
+            current = prev;
+            if current == source {
+                break;
+            }
+        }
+        path.reverse();
+        path
+    }
+
+    /// Shortest path (as a sequence of OSM node ids, inclusive of both ends)
+    /// from `source` to `target`, by road distance, or `None` if `target`
+    /// isn't reachable.
+    pub fn shortest_path(&self, source: i64, target: i64) -> Option<Vec<i64>> {
+        let (distances, predecessors) = self.shortest_paths_from(source, Metric::Distance);
+        distances.contains_key(&target).then(|| Self::reconstruct_path(&predecessors, source, target))
+    }
+
+    /// Run Dijkstra from every source node in parallel by road distance,
+    /// returning for each source a map of reachable node -> distance plus
+    /// the predecessor map.
+    pub fn many_to_many(&self, sources: &[i64]) -> Vec<(HashMap<i64, f64>, HashMap<i64, i64>)> {
+        self.many_to_many_by(sources, Metric::Distance)
+    }
+
+    /// Like [`Self::many_to_many`], but accumulates travel time (seconds)
+    /// instead of distance.
+    pub fn many_to_many_time(&self, sources: &[i64]) -> Vec<(HashMap<i64, f64>, HashMap<i64, i64>)> {
+        self.many_to_many_by(sources, Metric::Time)
+    }
+
+    fn many_to_many_by(&self, sources: &[i64], metric: Metric) -> Vec<(HashMap<i64, f64>, HashMap<i64, i64>)> {
+        sources
+            .par_iter()
+            .map(|&source| self.shortest_paths_from(source, metric))
+            .collect()
+    }
+}
+
+/// Free-flow speed (km/h) inferred from a way's `highway` value, used when
+/// the way has no usable `maxspeed` tag. Falls back to 50 km/h for values
+/// not listed here.
+fn highway_speed_kmh(highway: &str) -> f64 {
+    match highway {
+        "motorway" | "motorway_link" => 100.0,
+        "trunk" | "trunk_link" => 85.0,
+        "primary" | "primary_link" => 65.0,
+        "secondary" | "secondary_link" => 55.0,
+        "tertiary" | "tertiary_link" => 45.0,
+        "unclassified" | "residential" => 30.0,
+        "living_street" => 15.0,
+        "service" | "track" => 20.0,
+        "path" | "footway" | "cycleway" | "pedestrian" | "steps" => 5.0,
+        _ => 50.0,
+    }
+}
+
+/// Parses an OSM `maxspeed` tag (e.g. `"50"`, `"30 mph"`) into km/h.
+/// Returns `None` for forms this doesn't recognize (e.g. `"RU:urban"`
+/// implicit zone speeds), so the caller falls back to [`highway_speed_kmh`].
+fn parse_maxspeed_kmh(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Some(mph) = value.strip_suffix("mph").map(str::trim) {
+        return mph.parse::<f64>().ok().map(|v| v * 1.60934);
+    }
+    value
+        .strip_suffix("km/h")
+        .map(str::trim)
+        .unwrap_or(value)
+        .parse::<f64>()
+        .ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HeapEntry {
+    node: i64,
+    cost: f64,
+}
+
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse ordering to make BinaryHeap a min-heap on cost.
+        other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Result of snapping a [`VrpInstance`]'s locations onto a [`RoadGraph`].
+pub struct RoadNetworkMatrix {
+    /// True road distance in meters between each pair of locations.
+    pub distance_matrix: Vec<Vec<f64>>,
+    /// The node-level path (OSM node ids) between each pair of locations,
+    /// useful for drawing routes along real streets.
+    pub paths: Vec<Vec<Vec<i64>>>,
+    /// Locations whose nearest graph node turned out to be disconnected from
+    /// the rest of the graph, so their distances fell back to haversine.
+    pub disconnected_locations: Vec<usize>,
+}
+
+/// Snap every location in `instance` to the nearest node in `osm_data`, then
+/// run many-to-many Dijkstra over `graph` to fill `distance_matrix` (and
+/// `time_matrix`, if `average_speed_ms` is given) with true road distances
+/// and travel times, the latter honoring each road segment's tagged/inferred
+/// speed rather than one flat speed for the whole trip.
+///
+/// Locations that snap to a node with no path to another location fall back
+/// to a haversine distance (and `average_speed_ms`-based time) estimate for
+/// that pair, and are reported in `RoadNetworkMatrix::disconnected_locations`
+/// so callers can surface a warning.
+pub fn build_road_network_matrix(
+    instance: &mut VrpInstance,
+    osm_data: &OsmData,
+    graph: &RoadGraph,
+    average_speed_ms: Option<f64>,
+) -> VrpResult<RoadNetworkMatrix> {
+    let n = instance.locations.len();
+    if n == 0 {
+        return Err(VrpError::InvalidInput("No locations to route".to_string()));
+    }
+
+    // Snap each location to its nearest graph node.
+    let node_ids: Vec<i64> = instance
+        .locations
+        .iter()
+        .map(|location| nearest_graph_node(osm_data, graph, location.coordinate))
+        .collect::<VrpResult<Vec<_>>>()?;
+
+    let distance_results = graph.many_to_many(&node_ids);
+    let time_results = average_speed_ms.map(|_| graph.many_to_many_time(&node_ids));
+
+    let mut distance_matrix = vec![vec![0.0; n]; n];
+    let mut time_matrix = average_speed_ms.map(|_| vec![vec![0.0; n]; n]);
+    let mut paths: Vec<Vec<Vec<i64>>> = vec![vec![Vec::new(); n]; n];
+    let mut disconnected = Vec::new();
+
+    for i in 0..n {
+        let (distances, predecessors) = &distance_results[i];
+        let source_node = node_ids[i];
+        let mut row_disconnected = false;
+
+        for j in 0..n {
+            if i == j {
+                continue;
+            }
+            let target_node = node_ids[j];
+            match distances.get(&target_node) {
+                Some(&dist) => {
+                    distance_matrix[i][j] = dist;
+                    paths[i][j] = RoadGraph::reconstruct_path(predecessors, source_node, target_node);
+
+                    if let Some(time_matrix) = time_matrix.as_mut() {
+                        let time = time_results
+                            .as_ref()
+                            .and_then(|results| results[i].0.get(&target_node))
+                            .copied()
+                            .unwrap_or_else(|| dist / average_speed_ms.unwrap());
+                        time_matrix[i][j] = time;
+                    }
+                }
+                None => {
+                    // Disconnected snapping: fall back to haversine.
+                    let dist = haversine_distance(
+                        instance.locations[i].coordinate,
+                        instance.locations[j].coordinate,
+                    );
+                    distance_matrix[i][j] = dist;
+                    if let Some(time_matrix) = time_matrix.as_mut() {
+                        time_matrix[i][j] = dist / average_speed_ms.unwrap();
+                    }
+                    row_disconnected = true;
+                }
+            }
+        }
+
+        if row_disconnected {
+            disconnected.push(i);
+        }
+    }
+
+    instance.distance_matrix = distance_matrix.clone();
+    instance.time_matrix = time_matrix;
+
+    Ok(RoadNetworkMatrix {
+        distance_matrix,
+        paths,
+        disconnected_locations: disconnected,
+    })
+}
+
+fn nearest_graph_node(osm_data: &OsmData, graph: &RoadGraph, coordinate: Coordinate) -> VrpResult<i64> {
+    osm_data
+        .nodes
+        .values()
+        .filter(|node| graph.contains_node(node.id))
+        .map(|node| {
+            let dist = haversine_distance(coordinate, Coordinate::new(node.lat, node.lon));
+            (node.id, dist)
+        })
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+        .map(|(id, _)| id)
+        .ok_or_else(|| VrpError::InvalidInput("Road graph has no routable nodes".to_string()))
+}
@@ -0,0 +1,1390 @@
+//! Vehicle Routing Problem solving algorithms
+
+use crate::distance::{calculate_route_distance, calculate_route_duration, calculate_savings};
+use crate::local_search::LocalSearch;
+use crate::termination::{TerminationConfig, TerminationController, TerminationReason};
+use crate::types::{Location, Route, Solution, VrpInstance};
+use crate::{VrpError, VrpResult};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Time a vehicle leaves `location` after arriving at `arrival` (waiting for
+/// the window to open if early, then servicing it).
+fn departure_time(arrival: f64, location: &Location) -> f64 {
+    let start = location.time_window.map(|window| arrival.max(window.start)).unwrap_or(arrival);
+    start + location.service_time
+}
+
+/// Concatenates `left` and `right` for a Clarke-Wright merge, reversing
+/// whichever is needed so `left`'s tail becomes `left_tail` and `right`'s
+/// head becomes `right_head`. Returns `None` if one of those customers isn't
+/// actually at a route boundary, so the merge can't be oriented this way.
+fn oriented_concat(left: &[usize], right: &[usize], left_tail: usize, right_head: usize) -> Option<Vec<usize>> {
+    let mut left = left.to_vec();
+    if left.last() == Some(&left_tail) {
+        // already oriented
+    } else if left.first() == Some(&left_tail) {
+        left.reverse();
+    } else {
+        return None;
+    }
+
+    let mut right = right.to_vec();
+    if right.first() == Some(&right_head) {
+        // already oriented
+    } else if right.last() == Some(&right_head) {
+        right.reverse();
+    } else {
+        return None;
+    }
+
+    left.extend(right);
+    Some(left)
+}
+
+/// Whether visiting `route_indices`, starting from `depot_idx`, arrives at
+/// every stop within its time window. Always `true` when `instance` has no
+/// time matrix.
+fn route_respects_time_windows(instance: &VrpInstance, route_indices: &[usize], depot_idx: usize) -> bool {
+    let Some(time_matrix) = &instance.time_matrix else {
+        return true;
+    };
+
+    let mut current_idx = depot_idx;
+    let mut current_time = 0.0;
+    for &idx in route_indices {
+        current_time += time_matrix[current_idx][idx];
+        let location = &instance.locations[idx];
+        if let Some(window) = location.time_window {
+            if current_time > window.end {
+                return false;
+            }
+        }
+        current_time = departure_time(current_time, location);
+        current_idx = idx;
+    }
+    true
+}
+
+/// VRP Solver trait for different algorithms
+pub trait VrpSolver {
+    fn solve(&self, instance: &VrpInstance) -> VrpResult<Solution>;
+    fn name(&self) -> &'static str;
+}
+
+/// Greedy Nearest Neighbor algorithm
+#[derive(Debug)]
+pub struct GreedyNearestNeighbor {
+    /// Start with the farthest customer from depot
+    pub start_farthest: bool,
+    /// Blends pure nearest-distance selection (`1.0`, the default) with a
+    /// bias toward customers that keep the route close to the depot (`0.0`).
+    /// See [`with_greedy_factor`](Self::with_greedy_factor).
+    pub greedy_factor: f64,
+}
+
+impl Default for GreedyNearestNeighbor {
+    fn default() -> Self {
+        Self {
+            start_farthest: false,
+            greedy_factor: 1.0,
+        }
+    }
+}
+
+impl GreedyNearestNeighbor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_farthest_start(mut self, start_farthest: bool) -> Self {
+        self.start_farthest = start_farthest;
+        self
+    }
+
+    /// Sets the greedy/return-cost blend used to score the next customer:
+    /// `factor * distance(current, candidate) + (1 - factor) * distance(candidate, depot)`.
+    /// `1.0` is today's pure nearest-neighbor behavior; lower values favor
+    /// candidates that leave the route closer to the depot.
+    pub fn with_greedy_factor(mut self, greedy_factor: f64) -> Self {
+        self.greedy_factor = greedy_factor;
+        self
+    }
+
+    fn find_depot_index(&self, instance: &VrpInstance, vehicle_id: usize) -> VrpResult<usize> {
+        let vehicle = instance.get_vehicle(vehicle_id)
+            .ok_or_else(|| VrpError::InvalidInput(format!("Vehicle {} not found", vehicle_id)))?;
+        
+        instance.locations
+            .iter()
+            .position(|loc| loc.id == vehicle.depot_id)
+            .ok_or_else(|| VrpError::InvalidInput(format!("Depot {} not found", vehicle.depot_id)))
+    }
+
+    fn build_route(&self, instance: &VrpInstance, vehicle_id: usize, unvisited: &mut Vec<usize>) -> VrpResult<Route> {
+        let mut route = Route::new(vehicle_id);
+        let depot_idx = self.find_depot_index(instance, vehicle_id)?;
+        let vehicle = instance.get_vehicle(vehicle_id).unwrap();
+
+        if unvisited.is_empty() {
+            return Ok(route);
+        }
+
+        // Only a direct depot departure can reach a start candidate, so filter
+        // out any whose time window the depot-to-customer leg alone would miss.
+        let feasible_starts: Vec<usize> = unvisited.iter()
+            .copied()
+            .filter(|&idx| {
+                let Some(time_matrix) = &instance.time_matrix else {
+                    return true;
+                };
+                match instance.locations[idx].time_window {
+                    Some(window) => time_matrix[depot_idx][idx] <= window.end,
+                    None => true,
+                }
+            })
+            .collect();
+
+        if feasible_starts.is_empty() {
+            return Ok(route);
+        }
+
+        // Choose starting customer
+        let start_idx = if self.start_farthest {
+            // Find the customer farthest from depot
+            feasible_starts.iter()
+                .max_by(|&&a, &&b| {
+                    instance.distance_matrix[depot_idx][a]
+                        .partial_cmp(&instance.distance_matrix[depot_idx][b])
+                        .unwrap()
+                })
+                .copied()
+                .unwrap()
+        } else {
+            // Find the customer nearest to depot
+            feasible_starts.iter()
+                .min_by(|&&a, &&b| {
+                    instance.distance_matrix[depot_idx][a]
+                        .partial_cmp(&instance.distance_matrix[depot_idx][b])
+                        .unwrap()
+                })
+                .copied()
+                .unwrap()
+        };
+
+        // Add first customer
+        let start_location = &instance.locations[start_idx];
+        route.add_location(start_location.id);
+        route.total_demand += start_location.demand;
+        unvisited.retain(|&x| x != start_idx);
+
+        let mut current_idx = start_idx;
+        let mut current_time = instance.time_matrix.as_ref().map(|time_matrix| {
+            departure_time(time_matrix[depot_idx][start_idx], start_location)
+        });
+
+        // Greedy nearest neighbor selection
+        while !unvisited.is_empty() {
+            let mut best_next: Option<(usize, f64)> = None;
+
+            for &candidate_idx in unvisited.iter() {
+                let candidate_location = &instance.locations[candidate_idx];
+
+                // Check capacity constraint
+                if route.total_demand + candidate_location.demand > vehicle.capacity {
+                    continue;
+                }
+
+                // Check time window constraint
+                if let (Some(time_matrix), Some(time_so_far)) = (&instance.time_matrix, current_time) {
+                    if let Some(window) = candidate_location.time_window {
+                        let arrival = time_so_far + time_matrix[current_idx][candidate_idx];
+                        if arrival > window.end {
+                            continue;
+                        }
+                    }
+                }
+
+                let score = self.greedy_factor * instance.distance_matrix[current_idx][candidate_idx]
+                    + (1.0 - self.greedy_factor) * instance.distance_matrix[candidate_idx][depot_idx];
+
+                if best_next.is_none() || score < best_next.unwrap().1 {
+                    best_next = Some((candidate_idx, score));
+                }
+            }
+
+            if let Some((next_idx, _)) = best_next {
+                let next_location = &instance.locations[next_idx];
+                route.add_location(next_location.id);
+                route.total_demand += next_location.demand;
+                if let Some(time_matrix) = &instance.time_matrix {
+                    let travel_time = time_matrix[current_idx][next_idx];
+                    current_time = Some(departure_time(current_time.unwrap() + travel_time, next_location));
+                }
+                current_idx = next_idx;
+                unvisited.retain(|&x| x != next_idx);
+            } else {
+                // No more feasible customers for this vehicle
+                break;
+            }
+        }
+
+        // Calculate route metrics
+        let route_indices: Vec<usize> = route.locations
+            .iter()
+            .filter_map(|&id| instance.locations.iter().position(|loc| loc.id == id))
+            .collect();
+
+        route.total_distance = calculate_route_distance(instance, &route_indices, depot_idx);
+        
+        if let Some(duration) = calculate_route_duration(instance, &route_indices, depot_idx) {
+            route.total_duration = duration;
+        }
+
+        Ok(route)
+    }
+}
+
+impl VrpSolver for GreedyNearestNeighbor {
+    fn solve(&self, instance: &VrpInstance) -> VrpResult<Solution> {
+        let mut solution = Solution::new();
+        
+        // Get all customer indices (excluding depots)
+        let mut unvisited: Vec<usize> = instance.locations
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, loc)| {
+                if loc.demand > 0.0 { Some(idx) } else { None }
+            })
+            .collect();
+
+        // Build routes for each vehicle until all customers are served
+        for vehicle in &instance.vehicles {
+            if unvisited.is_empty() {
+                break;
+            }
+
+            let route = self.build_route(instance, vehicle.id, &mut unvisited)?;
+            solution.add_route(route);
+        }
+
+        if !unvisited.is_empty() {
+            return Err(VrpError::NoSolutionFound);
+        }
+
+        Ok(solution)
+    }
+
+    fn name(&self) -> &'static str {
+        "Greedy Nearest Neighbor"
+    }
+}
+
+/// Clarke-Wright Savings algorithm
+#[derive(Debug, Default)]
+pub struct ClarkeWrightSavings {
+    /// Whether to use parallel processing for savings calculation
+    pub parallel: bool,
+}
+
+impl ClarkeWrightSavings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    fn find_depot_index(&self, instance: &VrpInstance, depot_id: usize) -> VrpResult<usize> {
+        instance.locations
+            .iter()
+            .position(|loc| loc.id == depot_id)
+            .ok_or_else(|| VrpError::InvalidInput(format!("Depot {} not found", depot_id)))
+    }
+}
+
+impl VrpSolver for ClarkeWrightSavings {
+    fn solve(&self, instance: &VrpInstance) -> VrpResult<Solution> {
+        if instance.vehicles.is_empty() {
+            return Err(VrpError::InvalidInput("No vehicles available".to_string()));
+        }
+
+        let depot_id = instance.vehicles[0].depot_id;
+        let depot_idx = self.find_depot_index(instance, depot_id)?;
+
+        // Initialize: each customer has its own route
+        let mut routes: Vec<Route> = Vec::new();
+        let mut customer_to_route: HashMap<usize, usize> = HashMap::new();
+
+        for (idx, location) in instance.locations.iter().enumerate() {
+            if location.demand > 0.0 && idx != depot_idx {
+                let mut route = Route::new(0); // Will assign vehicle later
+                route.add_location(location.id);
+                route.total_demand = location.demand;
+                
+                // Calculate initial route metrics (depot -> customer -> depot)
+                route.total_distance = calculate_route_distance(instance, &[idx], depot_idx);
+                
+                if let Some(duration) = calculate_route_duration(instance, &[idx], depot_idx) {
+                    route.total_duration = duration;
+                }
+
+                customer_to_route.insert(location.id, routes.len());
+                routes.push(route);
+            }
+        }
+
+        // Calculate all savings, keeping only those worth acting on, and rank
+        // the best merges first. The `parallel` flag routes this through
+        // rayon instead of the sequential equivalents.
+        let mut savings = calculate_savings(instance, depot_id);
+
+        if self.parallel {
+            savings = savings.into_par_iter().filter(|s| s.value > 0.0).collect();
+            savings.par_sort_unstable_by(|a, b| b.value.partial_cmp(&a.value).unwrap());
+        } else {
+            savings.retain(|s| s.value > 0.0);
+            savings.sort_by(|a, b| b.value.partial_cmp(&a.value).unwrap());
+        }
+
+        // Process savings to merge routes
+        for saving in savings {
+            let route_i = customer_to_route.get(&saving.from).copied();
+            let route_j = customer_to_route.get(&saving.to).copied();
+
+            let (Some(ri), Some(rj)) = (route_i, route_j) else {
+                continue;
+            };
+            if ri == rj || ri >= routes.len() || rj >= routes.len() {
+                continue;
+            }
+
+            let route_i_ref = &routes[ri];
+            let route_j_ref = &routes[rj];
+
+            // Check if routes can be merged (capacity constraints)
+            let total_demand = route_i_ref.total_demand + route_j_ref.total_demand;
+            let can_merge = instance.vehicles.iter().any(|vehicle| total_demand <= vehicle.capacity);
+            if !can_merge {
+                continue;
+            }
+
+            // `from`/`to` might sit at either end of either route; reverse
+            // whichever route is needed so the saving pair ends up adjacent,
+            // trying every head/tail combination of the two routes.
+            let Some(merged_locations) = oriented_concat(&route_i_ref.locations, &route_j_ref.locations, saving.from, saving.to)
+                .or_else(|| oriented_concat(&route_i_ref.locations, &route_j_ref.locations, saving.to, saving.from))
+                .or_else(|| oriented_concat(&route_j_ref.locations, &route_i_ref.locations, saving.from, saving.to))
+                .or_else(|| oriented_concat(&route_j_ref.locations, &route_i_ref.locations, saving.to, saving.from))
+            else {
+                continue;
+            };
+
+            // Recalculate route metrics from the true distance matrix.
+            let route_indices: Vec<usize> = merged_locations.iter()
+                .filter_map(|&id| instance.locations.iter().position(|loc| loc.id == id))
+                .collect();
+
+            // Reject the merge if the combined route would miss a time window.
+            if !route_respects_time_windows(instance, &route_indices, depot_idx) {
+                continue;
+            }
+
+            let mut new_route = Route::new(0); // Will assign vehicle later
+            new_route.locations = merged_locations;
+            new_route.total_demand = total_demand;
+            new_route.total_distance = calculate_route_distance(instance, &route_indices, depot_idx);
+
+            if let Some(duration) = calculate_route_duration(instance, &route_indices, depot_idx) {
+                new_route.total_duration = duration;
+            }
+
+            // Update customer mappings
+            for &loc_id in &new_route.locations {
+                customer_to_route.insert(loc_id, ri);
+            }
+
+            // Replace route i with merged route and mark route j as empty
+            routes[ri] = new_route;
+            routes[rj] = Route::new(0); // Empty route
+        }
+
+        // Filter out empty routes and assign vehicles
+        let mut solution = Solution::new();
+        let mut vehicle_iter = instance.vehicles.iter();
+
+        for route in routes.into_iter().filter(|r| !r.is_empty()) {
+            if let Some(vehicle) = vehicle_iter.next() {
+                let mut final_route = route;
+                final_route.vehicle_id = vehicle.id;
+                solution.add_route(final_route);
+            }
+        }
+
+        if solution.routes.is_empty() {
+            Err(VrpError::NoSolutionFound)
+        } else {
+            Ok(solution)
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Clarke-Wright Savings"
+    }
+}
+
+/// Multi-start solver that runs multiple algorithms and returns the best solution
+#[derive(Default)]
+pub struct MultiStartSolver {
+    solvers: Vec<Box<dyn VrpSolver + Sync>>,
+    /// Local search passes run, in order, on each candidate solution before
+    /// it's compared against the others by `total_distance`.
+    local_search: Vec<Box<dyn LocalSearch + Sync>>,
+}
+
+impl MultiStartSolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_solver(mut self, solver: Box<dyn VrpSolver + Sync>) -> Self {
+        self.solvers.push(solver);
+        self
+    }
+
+    pub fn with_default_solvers(self) -> Self {
+        self.add_solver(Box::new(GreedyNearestNeighbor::new()))
+            .add_solver(Box::new(GreedyNearestNeighbor::new().with_farthest_start(true)))
+            .add_solver(Box::new(GreedyNearestNeighbor::new().with_greedy_factor(0.75)))
+            .add_solver(Box::new(GreedyNearestNeighbor::new().with_greedy_factor(0.25)))
+            .add_solver(Box::new(ClarkeWrightSavings::new().with_parallel(true)))
+    }
+
+    /// Append a local search pass to run on every candidate solution before
+    /// it's scored, e.g. `.add_local_search(Box::new(TwoOpt::new()))`.
+    pub fn add_local_search(mut self, pass: Box<dyn LocalSearch + Sync>) -> Self {
+        self.local_search.push(pass);
+        self
+    }
+
+    fn run_local_search(&self, mut solution: Solution, instance: &VrpInstance) -> Solution {
+        for pass in &self.local_search {
+            pass.improve(&mut solution, instance);
+        }
+        solution
+    }
+
+    /// Repeatedly run the configured solver set, tracking the best objective
+    /// seen so far in a [`TerminationController`], until a stopping
+    /// criterion (max iterations, max wall-clock time, or CV convergence)
+    /// fires. Returns the best solution found, which criterion stopped the
+    /// search, and how many iterations were run.
+    pub fn solve_with_termination(
+        &self,
+        instance: &VrpInstance,
+        config: TerminationConfig,
+    ) -> VrpResult<(Solution, TerminationReason, usize)> {
+        if self.solvers.is_empty() {
+            return Err(VrpError::InvalidInput("No solvers configured".to_string()));
+        }
+
+        let mut controller = TerminationController::new(config);
+        let mut best_solution: Option<Solution> = None;
+        let mut best_distance = f64::INFINITY;
+
+        loop {
+            let solution = self.solve(instance)?;
+            if solution.total_distance < best_distance {
+                best_distance = solution.total_distance;
+                best_solution = Some(solution);
+            }
+
+            if let Some(reason) = controller.record(best_distance) {
+                let solution = best_solution.ok_or(VrpError::NoSolutionFound)?;
+                return Ok((solution, reason, controller.iterations()));
+            }
+        }
+    }
+}
+
+impl VrpSolver for MultiStartSolver {
+    fn solve(&self, instance: &VrpInstance) -> VrpResult<Solution> {
+        if self.solvers.is_empty() {
+            return Err(VrpError::InvalidInput("No solvers configured".to_string()));
+        }
+
+        // Run all solvers in parallel, applying any configured local search
+        // passes to each candidate before it's scored.
+        let results: Vec<VrpResult<Solution>> = self.solvers
+            .par_iter()
+            .map(|solver| solver.solve(instance).map(|solution| self.run_local_search(solution, instance)))
+            .collect();
+
+        // Find the best valid solution
+        let mut best_solution: Option<Solution> = None;
+        let mut best_distance = f64::INFINITY;
+
+        for result in results {
+            if let Ok(solution) = result {
+                if solution.is_valid() && solution.total_distance < best_distance {
+                    best_distance = solution.total_distance;
+                    best_solution = Some(solution);
+                }
+            }
+        }
+
+        best_solution.ok_or(VrpError::NoSolutionFound)
+    }
+
+    fn name(&self) -> &'static str {
+        "Multi-Start Solver"
+    }
+}
+
+fn location_index(instance: &VrpInstance, location_id: usize) -> Option<usize> {
+    instance.locations.iter().position(|loc| loc.id == location_id)
+}
+
+/// Ruin-and-recreate metaheuristic: constructs an initial solution the same
+/// way `MultiStartSolver` does, then repeatedly ruins a random handful of
+/// customers out of their routes and greedily reinserts each at its
+/// cheapest feasible position across the whole fleet, keeping the move only
+/// if it improves total distance. Intended to be driven by
+/// [`Self::solve_with_termination`] (generation/time/CV budget); `solve`
+/// still runs a single generation for callers that only know the
+/// `VrpSolver` trait.
+pub struct MetaheuristicSolver {
+    /// Number of customers ruined and reinserted per generation.
+    pub ruin_count: usize,
+    seed: Option<u64>,
+}
+
+impl MetaheuristicSolver {
+    pub fn new() -> Self {
+        Self { ruin_count: 3, seed: None }
+    }
+
+    /// Fix the RNG seed so ruin/recreate choices are reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn rng(&self) -> rand::rngs::StdRng {
+        use rand::SeedableRng;
+        match self.seed {
+            Some(seed) => rand::rngs::StdRng::seed_from_u64(seed),
+            None => rand::rngs::StdRng::from_entropy(),
+        }
+    }
+
+    fn construct_initial(&self, instance: &VrpInstance) -> VrpResult<Solution> {
+        MultiStartSolver::new().with_default_solvers().solve(instance)
+    }
+
+    /// Total customers served across every route. [`Solution::is_valid`]
+    /// doesn't check this, so callers comparing a ruined-and-recreated
+    /// candidate against its incumbent must check it themselves — otherwise
+    /// a candidate that silently dropped a stop looks strictly better
+    /// (shorter) and gets accepted.
+    fn customers_served(solution: &Solution) -> usize {
+        solution.routes.iter().map(|r| r.locations.len()).sum()
+    }
+
+    fn recompute_route(&self, instance: &VrpInstance, route: &mut Route) {
+        let Some(vehicle) = instance.get_vehicle(route.vehicle_id) else { return };
+        let Some(depot_idx) = location_index(instance, vehicle.depot_id) else { return };
+
+        let route_indices: Vec<usize> = route.locations.iter()
+            .filter_map(|&id| location_index(instance, id))
+            .collect();
+
+        route.total_distance = calculate_route_distance(instance, &route_indices, depot_idx);
+        if let Some(duration) = calculate_route_duration(instance, &route_indices, depot_idx) {
+            route.total_duration = duration;
+        }
+        route.total_demand = route_indices.iter().map(|&idx| instance.locations[idx].demand).sum();
+    }
+
+    /// One ruin-and-recreate generation atop `solution`. Every ruined
+    /// customer is reinserted somewhere if any route has room; one that
+    /// doesn't fit anywhere is left out of the returned candidate, so
+    /// callers must compare `customers_served` against the incumbent (not
+    /// just `total_distance`) before accepting it.
+    fn ruin_and_recreate(&self, instance: &VrpInstance, solution: &Solution, rng: &mut rand::rngs::StdRng) -> Solution {
+        use rand::seq::SliceRandom;
+
+        let mut routes = solution.routes.clone();
+
+        let mut all_customers: Vec<usize> = routes.iter().flat_map(|r| r.locations.iter().copied()).collect();
+        if all_customers.is_empty() {
+            return solution.clone();
+        }
+        all_customers.shuffle(rng);
+        let ruin_count = self.ruin_count.min(all_customers.len());
+        let ruined: Vec<usize> = all_customers.into_iter().take(ruin_count).collect();
+
+        for route in routes.iter_mut() {
+            route.locations.retain(|id| !ruined.contains(id));
+            self.recompute_route(instance, route);
+        }
+
+        for customer_id in ruined {
+            let Some(customer_idx) = location_index(instance, customer_id) else { continue };
+            let demand = instance.locations[customer_idx].demand;
+
+            // Cheapest-insertion: try every position in every route, keep
+            // whichever adds the least distance while respecting capacity.
+            let mut best: Option<(usize, usize, f64)> = None;
+
+            for (route_idx, route) in routes.iter().enumerate() {
+                let Some(vehicle) = instance.get_vehicle(route.vehicle_id) else { continue };
+                if route.total_demand + demand > vehicle.capacity {
+                    continue;
+                }
+                let Some(depot_idx) = location_index(instance, vehicle.depot_id) else { continue };
+
+                let route_indices: Vec<usize> = route.locations.iter()
+                    .filter_map(|&id| location_index(instance, id))
+                    .collect();
+
+                for pos in 0..=route_indices.len() {
+                    let prev = if pos == 0 { depot_idx } else { route_indices[pos - 1] };
+                    let next = if pos == route_indices.len() { depot_idx } else { route_indices[pos] };
+                    let added = instance.distance_matrix[prev][customer_idx]
+                        + instance.distance_matrix[customer_idx][next]
+                        - instance.distance_matrix[prev][next];
+
+                    if best.map(|(_, _, cost)| added < cost).unwrap_or(true) {
+                        best = Some((route_idx, pos, added));
+                    }
+                }
+            }
+
+            if let Some((route_idx, pos, _)) = best {
+                routes[route_idx].locations.insert(pos, customer_id);
+                self.recompute_route(instance, &mut routes[route_idx]);
+            }
+            // If no route has room, the customer is left out of this
+            // candidate; `customers_served` lets callers detect and reject
+            // that instead of mistaking the now-shorter route for an
+            // improvement.
+        }
+
+        let mut candidate = Solution::new();
+        for route in routes {
+            candidate.add_route(route);
+        }
+        candidate
+    }
+
+    /// Run ruin-and-recreate generations under a [`TerminationController`]
+    /// until a stopping criterion fires, returning the best solution found,
+    /// which criterion stopped the search, and how many generations ran.
+    pub fn solve_with_termination(
+        &self,
+        instance: &VrpInstance,
+        config: TerminationConfig,
+    ) -> VrpResult<(Solution, TerminationReason, usize)> {
+        self.run_generations(instance, config, None, |_, _| {})
+    }
+
+    /// Same search as [`Self::solve_with_termination`], but checked against
+    /// `stop` between generations (for cooperative cancellation from a
+    /// background job) and reporting the best cost and generation count
+    /// after every generation via `on_progress`.
+    pub fn solve_with_progress(
+        &self,
+        instance: &VrpInstance,
+        config: TerminationConfig,
+        stop: &AtomicBool,
+        on_progress: impl FnMut(f64, usize),
+    ) -> VrpResult<(Solution, TerminationReason, usize)> {
+        self.run_generations(instance, config, Some(stop), on_progress)
+    }
+
+    fn run_generations(
+        &self,
+        instance: &VrpInstance,
+        config: TerminationConfig,
+        stop: Option<&AtomicBool>,
+        mut on_progress: impl FnMut(f64, usize),
+    ) -> VrpResult<(Solution, TerminationReason, usize)> {
+        let mut rng = self.rng();
+        let mut best = self.construct_initial(instance)?;
+        let mut controller = TerminationController::new(config);
+
+        loop {
+            if stop.map(|flag| flag.load(Ordering::Relaxed)).unwrap_or(false) {
+                return Ok((best, TerminationReason::Cancelled, controller.iterations()));
+            }
+
+            let candidate = self.ruin_and_recreate(instance, &best, &mut rng);
+            if candidate.is_valid()
+                && Self::customers_served(&candidate) >= Self::customers_served(&best)
+                && candidate.total_distance < best.total_distance
+            {
+                best = candidate;
+            }
+
+            if let Some(reason) = controller.record(best.total_distance) {
+                on_progress(best.total_distance, controller.iterations());
+                return Ok((best, reason, controller.iterations()));
+            }
+            on_progress(best.total_distance, controller.iterations());
+        }
+    }
+}
+
+impl Default for MetaheuristicSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VrpSolver for MetaheuristicSolver {
+    fn solve(&self, instance: &VrpInstance) -> VrpResult<Solution> {
+        let mut rng = self.rng();
+        let initial = self.construct_initial(instance)?;
+        let candidate = self.ruin_and_recreate(instance, &initial, &mut rng);
+
+        if candidate.is_valid()
+            && Self::customers_served(&candidate) >= Self::customers_served(&initial)
+            && candidate.total_distance < initial.total_distance
+        {
+            Ok(candidate)
+        } else {
+            Ok(initial)
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Metaheuristic (Ruin & Recreate)"
+    }
+}
+
+/// Simulated Annealing: starts from a construction solution and explores the
+/// neighborhood via random intra-route 2-opt swaps and inter-route
+/// relocation/exchange moves, accepting improving moves unconditionally and
+/// worsening ones with probability `exp(-delta / temperature)`. Temperature
+/// cools by `cooling_rate` every step; the best feasible solution seen over
+/// the run is returned regardless of where the walk ends up.
+pub struct SimulatedAnnealing {
+    pub initial_temperature: f64,
+    pub cooling_rate: f64,
+    pub iterations: usize,
+    seed: Option<u64>,
+}
+
+impl SimulatedAnnealing {
+    pub fn new() -> Self {
+        Self {
+            initial_temperature: 1000.0,
+            cooling_rate: 0.995,
+            iterations: 1000,
+            seed: None,
+        }
+    }
+
+    pub fn with_initial_temperature(mut self, temperature: f64) -> Self {
+        self.initial_temperature = temperature;
+        self
+    }
+
+    pub fn with_cooling_rate(mut self, cooling_rate: f64) -> Self {
+        self.cooling_rate = cooling_rate;
+        self
+    }
+
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Fix the RNG seed so the random walk is reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn rng(&self) -> rand::rngs::StdRng {
+        use rand::SeedableRng;
+        match self.seed {
+            Some(seed) => rand::rngs::StdRng::seed_from_u64(seed),
+            None => rand::rngs::StdRng::from_entropy(),
+        }
+    }
+
+    fn recompute_route(&self, instance: &VrpInstance, route: &mut Route) {
+        let Some(vehicle) = instance.get_vehicle(route.vehicle_id) else { return };
+        let Some(depot_idx) = location_index(instance, vehicle.depot_id) else { return };
+
+        let route_indices: Vec<usize> = route.locations.iter()
+            .filter_map(|&id| location_index(instance, id))
+            .collect();
+
+        route.total_distance = calculate_route_distance(instance, &route_indices, depot_idx);
+        if let Some(duration) = calculate_route_duration(instance, &route_indices, depot_idx) {
+            route.total_duration = duration;
+        }
+        route.total_demand = route_indices.iter().map(|&idx| instance.locations[idx].demand).sum();
+    }
+
+    /// Apply one random neighborhood move to a clone of `routes`, respecting
+    /// vehicle capacity. Returns `None` if the chosen move wasn't applicable
+    /// (e.g. not enough routes/customers for it), in which case the caller
+    /// should treat the step as a no-op.
+    fn propose_move(&self, instance: &VrpInstance, routes: &[Route], rng: &mut rand::rngs::StdRng) -> Option<Vec<Route>> {
+        use rand::Rng;
+
+        let mut routes = routes.to_vec();
+        let move_kind = rng.gen_range(0..3);
+
+        match move_kind {
+            // Intra-route 2-opt: reverse a random segment of one route.
+            0 => {
+                let non_empty: Vec<usize> = routes.iter().enumerate()
+                    .filter(|(_, r)| r.locations.len() >= 2)
+                    .map(|(idx, _)| idx)
+                    .collect();
+                let &route_idx = non_empty.get(rng.gen_range(0..non_empty.len().max(1)))?;
+
+                let route = &mut routes[route_idx];
+                let n = route.locations.len();
+                let i = rng.gen_range(0..n);
+                let j = rng.gen_range(0..n);
+                let (i, j) = if i < j { (i, j) } else { (j, i) };
+                if i == j {
+                    return None;
+                }
+                route.locations[i..=j].reverse();
+                self.recompute_route(instance, route);
+                Some(routes)
+            }
+            // Inter-route relocation: move one customer to a different route.
+            1 => {
+                if routes.len() < 2 {
+                    return None;
+                }
+                let from = rng.gen_range(0..routes.len());
+                let to = rng.gen_range(0..routes.len() - 1);
+                let to = if to >= from { to + 1 } else { to };
+
+                if routes[from].locations.is_empty() {
+                    return None;
+                }
+                let pos = rng.gen_range(0..routes[from].locations.len());
+                let customer_id = routes[from].locations[pos];
+                let Some(customer_idx) = location_index(instance, customer_id) else { return None };
+                let demand = instance.locations[customer_idx].demand;
+
+                let to_vehicle = instance.get_vehicle(routes[to].vehicle_id)?;
+                if routes[to].total_demand + demand > to_vehicle.capacity {
+                    return None;
+                }
+
+                routes[from].locations.remove(pos);
+                let insert_at = rng.gen_range(0..=routes[to].locations.len());
+                routes[to].locations.insert(insert_at, customer_id);
+
+                self.recompute_route(instance, &mut routes[from]);
+                self.recompute_route(instance, &mut routes[to]);
+                Some(routes)
+            }
+            // Inter-route exchange: swap one customer between two routes.
+            _ => {
+                if routes.len() < 2 {
+                    return None;
+                }
+                let a = rng.gen_range(0..routes.len());
+                let b = rng.gen_range(0..routes.len() - 1);
+                let b = if b >= a { b + 1 } else { b };
+
+                if routes[a].locations.is_empty() || routes[b].locations.is_empty() {
+                    return None;
+                }
+                let pos_a = rng.gen_range(0..routes[a].locations.len());
+                let pos_b = rng.gen_range(0..routes[b].locations.len());
+                let customer_a = routes[a].locations[pos_a];
+                let customer_b = routes[b].locations[pos_b];
+                let demand_a = location_index(instance, customer_a).map(|idx| instance.locations[idx].demand)?;
+                let demand_b = location_index(instance, customer_b).map(|idx| instance.locations[idx].demand)?;
+
+                let vehicle_a = instance.get_vehicle(routes[a].vehicle_id)?;
+                let vehicle_b = instance.get_vehicle(routes[b].vehicle_id)?;
+                if routes[a].total_demand - demand_a + demand_b > vehicle_a.capacity {
+                    return None;
+                }
+                if routes[b].total_demand - demand_b + demand_a > vehicle_b.capacity {
+                    return None;
+                }
+
+                routes[a].locations[pos_a] = customer_b;
+                routes[b].locations[pos_b] = customer_a;
+
+                self.recompute_route(instance, &mut routes[a]);
+                self.recompute_route(instance, &mut routes[b]);
+                Some(routes)
+            }
+        }
+    }
+}
+
+impl Default for SimulatedAnnealing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VrpSolver for SimulatedAnnealing {
+    fn solve(&self, instance: &VrpInstance) -> VrpResult<Solution> {
+        use rand::Rng;
+
+        let mut rng = self.rng();
+        let initial = GreedyNearestNeighbor::new().solve(instance)?;
+
+        let mut current = initial.routes.clone();
+        let mut current_distance = initial.total_distance;
+        let mut best = initial.clone();
+        let mut temperature = self.initial_temperature;
+
+        for _ in 0..self.iterations {
+            if let Some(candidate_routes) = self.propose_move(instance, &current, &mut rng) {
+                let candidate_distance: f64 = candidate_routes.iter().map(|r| r.total_distance).sum();
+                let delta = candidate_distance - current_distance;
+
+                let accept = delta < 0.0
+                    || rng.gen::<f64>() < (-delta / temperature.max(f64::EPSILON)).exp();
+
+                if accept {
+                    current = candidate_routes;
+                    current_distance = candidate_distance;
+
+                    if current_distance < best.total_distance {
+                        let mut candidate_solution = Solution::new();
+                        for route in &current {
+                            candidate_solution.add_route(route.clone());
+                        }
+                        if candidate_solution.is_valid() {
+                            best = candidate_solution;
+                        }
+                    }
+                }
+            }
+
+            temperature *= self.cooling_rate;
+        }
+
+        Ok(best)
+    }
+
+    fn name(&self) -> &'static str {
+        "Simulated Annealing"
+    }
+}
+
+/// Exact Held-Karp bitmask dynamic programming for small single-vehicle
+/// instances: provably optimal whenever it applies, as a baseline to
+/// validate the heuristic solvers against. `dp[mask][j]` is the minimum cost
+/// to start at the depot, visit exactly the customer set `mask`, and end at
+/// customer `j`; the tour order is reconstructed from stored predecessors.
+#[derive(Debug, Default)]
+pub struct HeldKarp;
+
+impl HeldKarp {
+    /// The DP has `2^n` states, so instances larger than this are rejected
+    /// rather than left to run out of memory.
+    pub const MAX_CUSTOMERS: usize = 15;
+
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl VrpSolver for HeldKarp {
+    fn solve(&self, instance: &VrpInstance) -> VrpResult<Solution> {
+        if instance.vehicles.len() != 1 {
+            return Err(VrpError::InvalidInput(
+                "Held-Karp requires exactly one vehicle".to_string(),
+            ));
+        }
+        let vehicle = &instance.vehicles[0];
+
+        let customer_indices: Vec<usize> = instance.locations.iter()
+            .enumerate()
+            .filter_map(|(idx, loc)| if loc.demand > 0.0 { Some(idx) } else { None })
+            .collect();
+
+        let total_demand: f64 = customer_indices.iter().map(|&idx| instance.locations[idx].demand).sum();
+        if total_demand > vehicle.capacity {
+            return Err(VrpError::CapacityViolation { required: total_demand, available: vehicle.capacity });
+        }
+
+        let n = customer_indices.len();
+        if n > Self::MAX_CUSTOMERS {
+            return Err(VrpError::InvalidInput(format!(
+                "Held-Karp only supports up to {} customers, got {}",
+                Self::MAX_CUSTOMERS,
+                n,
+            )));
+        }
+
+        let depot_idx = location_index(instance, vehicle.depot_id)
+            .ok_or_else(|| VrpError::InvalidInput(format!("Depot {} not found", vehicle.depot_id)))?;
+
+        if n == 0 {
+            return Ok(Solution::new());
+        }
+
+        let num_masks = 1usize << n;
+        let mut dp = vec![vec![f64::INFINITY; n]; num_masks];
+        let mut parent: Vec<Vec<Option<usize>>> = vec![vec![None; n]; num_masks];
+
+        for j in 0..n {
+            dp[1 << j][j] = instance.distance_matrix[depot_idx][customer_indices[j]];
+        }
+
+        for mask in 1..num_masks {
+            for j in 0..n {
+                if mask & (1 << j) == 0 || dp[mask][j].is_infinite() {
+                    continue;
+                }
+                for k in 0..n {
+                    if mask & (1 << k) != 0 {
+                        continue;
+                    }
+                    let next_mask = mask | (1 << k);
+                    let cost = dp[mask][j] + instance.distance_matrix[customer_indices[j]][customer_indices[k]];
+                    if cost < dp[next_mask][k] {
+                        dp[next_mask][k] = cost;
+                        parent[next_mask][k] = Some(j);
+                    }
+                }
+            }
+        }
+
+        let full_mask = num_masks - 1;
+        let (best_j, best_cost) = (0..n)
+            .map(|j| (j, dp[full_mask][j] + instance.distance_matrix[customer_indices[j]][depot_idx]))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .unwrap();
+
+        if best_cost.is_infinite() {
+            return Err(VrpError::NoSolutionFound);
+        }
+
+        let mut order = Vec::with_capacity(n);
+        let mut mask = full_mask;
+        let mut j = best_j;
+        loop {
+            order.push(customer_indices[j]);
+            let prev = parent[mask][j];
+            mask &= !(1 << j);
+            match prev {
+                Some(prev_j) => j = prev_j,
+                None => break,
+            }
+        }
+        order.reverse();
+
+        let mut route = Route::new(vehicle.id);
+        for &idx in &order {
+            route.add_location(instance.locations[idx].id);
+        }
+        route.total_demand = total_demand;
+        route.total_distance = calculate_route_distance(instance, &order, depot_idx);
+        if let Some(duration) = calculate_route_duration(instance, &order, depot_idx) {
+            route.total_duration = duration;
+        }
+
+        let mut solution = Solution::new();
+        solution.add_route(route);
+        Ok(solution)
+    }
+
+    fn name(&self) -> &'static str {
+        "Held-Karp (Exact)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::{calculate_distance_matrix, calculate_time_matrix, DistanceMethod};
+    use crate::types::*;
+    use crate::validate::validate_solution;
+
+    fn create_test_instance() -> VrpInstance {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(1.0, 1.0), 10.0, None, 5.0),
+            Location::new(2, "Customer 2".to_string(), Coordinate::new(2.0, 2.0), 15.0, None, 5.0),
+            Location::new(3, "Customer 3".to_string(), Coordinate::new(-1.0, 1.0), 8.0, None, 5.0),
+        ];
+
+        let vehicles = vec![
+            Vehicle::new(0, 50.0, None, None, 0),
+            Vehicle::new(1, 30.0, None, None, 0),
+        ];
+
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+        instance
+    }
+
+    #[test]
+    fn test_greedy_nearest_neighbor() {
+        let instance = create_test_instance();
+        let solver = GreedyNearestNeighbor::new();
+        
+        let result = solver.solve(&instance);
+        assert!(result.is_ok());
+        
+        let solution = result.unwrap();
+        assert!(solution.is_valid());
+        assert!(!solution.routes.is_empty());
+    }
+
+    #[test]
+    fn test_greedy_factor_biases_toward_depot_proximity() {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Start".to_string(), Coordinate::new(1.0, 0.0), 5.0, None, 0.0),
+            Location::new(2, "Near current, far depot".to_string(), Coordinate::new(1.2, 0.0), 5.0, None, 0.0),
+            Location::new(3, "Far current, near depot".to_string(), Coordinate::new(0.0, 1.1), 5.0, None, 0.0),
+        ];
+        let vehicles = vec![Vehicle::new(0, 100.0, None, None, 0)];
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+
+        // Pure greedy (factor 1.0) always hops to whichever customer is nearest.
+        let pure_greedy = GreedyNearestNeighbor::new().with_greedy_factor(1.0).solve(&instance).unwrap();
+        assert_eq!(pure_greedy.routes[0].locations, vec![1, 2, 3]);
+
+        // Full depot bias (factor 0.0) prefers the customer that leaves the route
+        // closer to the depot, even though it's farther from the current stop.
+        let depot_biased = GreedyNearestNeighbor::new().with_greedy_factor(0.0).solve(&instance).unwrap();
+        assert_eq!(depot_biased.routes[0].locations, vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn test_clarke_wright_savings() {
+        let instance = create_test_instance();
+        let solver = ClarkeWrightSavings::new();
+        
+        let result = solver.solve(&instance);
+        assert!(result.is_ok());
+        
+        let solution = result.unwrap();
+        assert!(solution.is_valid());
+    }
+
+    #[test]
+    fn test_clarke_wright_savings_merges_colinear_customers() {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "A".to_string(), Coordinate::new(1.0, 0.0), 5.0, None, 0.0),
+            Location::new(2, "B".to_string(), Coordinate::new(2.0, 0.0), 5.0, None, 0.0),
+        ];
+        let vehicles = vec![Vehicle::new(0, 50.0, None, None, 0)];
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+
+        let solution = ClarkeWrightSavings::new().solve(&instance).unwrap();
+
+        assert_eq!(solution.routes.len(), 1);
+        assert_eq!(solution.routes[0].locations.len(), 2);
+    }
+
+    #[test]
+    fn test_clarke_wright_savings_parallel_matches_sequential_route_count() {
+        let instance = create_test_instance();
+        let sequential = ClarkeWrightSavings::new().solve(&instance).unwrap();
+        let parallel = ClarkeWrightSavings::new().with_parallel(true).solve(&instance).unwrap();
+
+        assert_eq!(sequential.routes.len(), parallel.routes.len());
+    }
+
+    #[test]
+    fn test_oriented_concat_reverses_when_both_endpoints_are_route_starts() {
+        let left = vec![3, 1];
+        let right = vec![2, 4];
+
+        let merged = oriented_concat(&left, &right, 3, 2).unwrap();
+
+        assert_eq!(merged, vec![1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn test_oriented_concat_rejects_interior_endpoint() {
+        let left = vec![1, 2, 3];
+        let right = vec![4, 5];
+
+        assert!(oriented_concat(&left, &right, 2, 4).is_none());
+    }
+
+    #[test]
+    fn test_multi_start_solver() {
+        let instance = create_test_instance();
+        let solver = MultiStartSolver::new().with_default_solvers();
+
+        let result = solver.solve(&instance);
+        assert!(result.is_ok());
+
+        let solution = result.unwrap();
+        assert!(solution.is_valid());
+    }
+
+    #[test]
+    fn test_metaheuristic_solver() {
+        let instance = create_test_instance();
+        let solver = MetaheuristicSolver::new().with_seed(42);
+
+        let result = solver.solve(&instance);
+        assert!(result.is_ok());
+
+        let solution = result.unwrap();
+        assert!(solution.is_valid());
+    }
+
+    #[test]
+    fn test_metaheuristic_solver_with_termination() {
+        let instance = create_test_instance();
+        let solver = MetaheuristicSolver::new().with_seed(7);
+        let config = TerminationConfig {
+            max_iterations: Some(20),
+            max_time_secs: None,
+            min_cv: None,
+            window_size: 5,
+        };
+
+        let result = solver.solve_with_termination(&instance, config);
+        assert!(result.is_ok());
+
+        let (solution, reason, iterations) = result.unwrap();
+        assert!(solution.is_valid());
+        assert_eq!(reason, TerminationReason::MaxIterations);
+        assert_eq!(iterations, 20);
+    }
+
+    #[test]
+    fn test_metaheuristic_solver_honors_stop_flag() {
+        let instance = create_test_instance();
+        let solver = MetaheuristicSolver::new().with_seed(7);
+        let config = TerminationConfig {
+            max_iterations: Some(1000),
+            max_time_secs: None,
+            min_cv: None,
+            window_size: 5,
+        };
+        let stop = AtomicBool::new(true);
+
+        let result = solver.solve_with_progress(&instance, config, &stop, |_, _| {});
+        assert!(result.is_ok());
+
+        let (solution, reason, iterations) = result.unwrap();
+        assert!(solution.is_valid());
+        assert_eq!(reason, TerminationReason::Cancelled);
+        assert_eq!(iterations, 0);
+    }
+
+    #[test]
+    fn test_simulated_annealing() {
+        let instance = create_test_instance();
+        let solver = SimulatedAnnealing::new().with_seed(42).with_iterations(200);
+
+        let result = solver.solve(&instance);
+        assert!(result.is_ok());
+
+        let solution = result.unwrap();
+        assert!(solution.is_valid());
+    }
+
+    #[test]
+    fn test_simulated_annealing_does_not_worsen_best() {
+        let instance = create_test_instance();
+        let initial = GreedyNearestNeighbor::new().solve(&instance).unwrap();
+        let solver = SimulatedAnnealing::new().with_seed(99).with_iterations(500);
+
+        let result = solver.solve(&instance).unwrap();
+        assert!(result.total_distance <= initial.total_distance + 1e-6);
+    }
+
+    #[test]
+    fn test_held_karp_finds_optimal_single_vehicle_route() {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(1.0, 0.0), 10.0, None, 5.0),
+            Location::new(2, "Customer 2".to_string(), Coordinate::new(2.0, 0.0), 10.0, None, 5.0),
+            Location::new(3, "Customer 3".to_string(), Coordinate::new(1.0, 1.0), 10.0, None, 5.0),
+        ];
+        let vehicles = vec![Vehicle::new(0, 100.0, None, None, 0)];
+
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+
+        let result = HeldKarp::new().solve(&instance);
+        assert!(result.is_ok());
+
+        let solution = result.unwrap();
+        assert!(solution.is_valid());
+        assert_eq!(solution.routes[0].locations.len(), 3);
+
+        // The DP is provably optimal, so it must be at least as good as a
+        // greedy construction on the same instance.
+        let greedy = GreedyNearestNeighbor::new().solve(&instance).unwrap();
+        assert!(solution.total_distance <= greedy.total_distance + 1e-9);
+    }
+
+    #[test]
+    fn test_held_karp_rejects_multi_vehicle_instance() {
+        let instance = create_test_instance();
+        let result = HeldKarp::new().solve(&instance);
+        assert!(matches!(result, Err(VrpError::InvalidInput(_))));
+    }
+
+    fn create_time_windowed_instance() -> VrpInstance {
+        // Customer 2 is reachable directly from the depot within its window,
+        // but only if nothing is visited first: a detour via customer 1 (whose
+        // long service time eats the slack) arrives too late.
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(1.0, 0.0), 5.0, None, 5.0),
+            Location::new(2, "Customer 2".to_string(), Coordinate::new(3.0, 0.0), 5.0, Some(TimeWindow::new(0.0, 5.0)), 0.0),
+        ];
+        let vehicles = vec![
+            Vehicle::new(0, 50.0, None, None, 0),
+            Vehicle::new(1, 50.0, None, None, 0),
+        ];
+
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+        // Euclidean distances are in meters (111,320 per degree of latitude),
+        // so this speed makes each time equal the latitude difference in degrees.
+        calculate_time_matrix(&mut instance, 111_320.0);
+        instance
+    }
+
+    #[test]
+    fn test_greedy_nearest_neighbor_respects_time_windows() {
+        let instance = create_time_windowed_instance();
+        let solution = GreedyNearestNeighbor::new().solve(&instance).unwrap();
+
+        assert!(validate_solution(&instance, &solution).unwrap());
+        // Customer 2 must not be stranded behind customer 1's long service time.
+        assert!(!solution.routes.iter().any(|r| r.locations == vec![1, 2]));
+    }
+
+    #[test]
+    fn test_clarke_wright_savings_respects_time_windows() {
+        let instance = create_time_windowed_instance();
+        let solution = ClarkeWrightSavings::new().solve(&instance).unwrap();
+
+        assert!(validate_solution(&instance, &solution).unwrap());
+        // Merging customer 1 ahead of customer 2 would blow the time window.
+        assert!(!solution.routes.iter().any(|r| r.locations == vec![1, 2]));
+    }
+}
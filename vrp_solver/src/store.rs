@@ -0,0 +1,332 @@
+//! Pluggable persistence backend for [`crate::app_state::AppState`]
+//!
+//! `Store` captures the CRUD operations `AppState` used to hard-code
+//! directly against an in-memory `HashMap`. [`InMemoryStore`] preserves that
+//! original behavior; [`SledStore`] persists the same entities to disk so a
+//! server restart doesn't lose every uploaded graph, instance, and solution.
+
+use crate::api_types::{AppStateStats, StoredGraph, StoredMapping, StoredSolution, StoredVrpInstance};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+/// CRUD operations `AppState` needs from a persistence backend. Every method
+/// takes `&self` (not `&mut self`) so a `Store` can be shared behind an
+/// `Arc` the same way the original `Arc<RwLock<HashMap<..>>>` fields were.
+pub trait Store: Send + Sync {
+    fn store_graph(&self, graph: StoredGraph) -> Result<Uuid, String>;
+    fn get_graph(&self, graph_id: &Uuid) -> Result<Option<StoredGraph>, String>;
+    fn list_graphs(&self) -> Result<Vec<Uuid>, String>;
+
+    fn store_mapping(&self, mapping: StoredMapping) -> Result<(), String>;
+    fn get_mapping(&self, graph_id: &Uuid) -> Result<Option<StoredMapping>, String>;
+
+    fn store_vrp_instance(&self, instance: StoredVrpInstance) -> Result<Uuid, String>;
+    fn get_vrp_instance(&self, vrp_id: &Uuid) -> Result<Option<StoredVrpInstance>, String>;
+    fn list_vrp_instances(&self) -> Result<Vec<Uuid>, String>;
+
+    fn store_solution(&self, solution: StoredSolution) -> Result<Uuid, String>;
+    fn get_solution(&self, solution_id: &Uuid) -> Result<Option<StoredSolution>, String>;
+    fn list_solutions(&self) -> Result<Vec<Uuid>, String>;
+    fn get_solutions_for_vrp(&self, vrp_id: &Uuid) -> Result<Vec<StoredSolution>, String>;
+
+    fn cleanup_old_data(&self, max_age_hours: u64) -> Result<(), String>;
+    fn get_stats(&self) -> Result<AppStateStats, String>;
+}
+
+/// The original in-memory backend: everything lives in
+/// `RwLock<HashMap<Uuid, T>>` fields and is lost on restart.
+#[derive(Debug, Default)]
+pub struct InMemoryStore {
+    graphs: RwLock<HashMap<Uuid, StoredGraph>>,
+    mappings: RwLock<HashMap<Uuid, StoredMapping>>,
+    vrp_instances: RwLock<HashMap<Uuid, StoredVrpInstance>>,
+    solutions: RwLock<HashMap<Uuid, StoredSolution>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Store for InMemoryStore {
+    fn store_graph(&self, graph: StoredGraph) -> Result<Uuid, String> {
+        let graph_id = graph.id;
+        self.graphs.write()
+            .map_err(|_| "Failed to acquire write lock for graphs".to_string())?
+            .insert(graph_id, graph);
+        Ok(graph_id)
+    }
+
+    fn get_graph(&self, graph_id: &Uuid) -> Result<Option<StoredGraph>, String> {
+        Ok(self.graphs.read()
+            .map_err(|_| "Failed to acquire read lock for graphs".to_string())?
+            .get(graph_id)
+            .cloned())
+    }
+
+    fn list_graphs(&self) -> Result<Vec<Uuid>, String> {
+        Ok(self.graphs.read()
+            .map_err(|_| "Failed to acquire read lock for graphs".to_string())?
+            .keys()
+            .cloned()
+            .collect())
+    }
+
+    fn store_mapping(&self, mapping: StoredMapping) -> Result<(), String> {
+        self.mappings.write()
+            .map_err(|_| "Failed to acquire write lock for mappings".to_string())?
+            .insert(mapping.graph_id, mapping);
+        Ok(())
+    }
+
+    fn get_mapping(&self, graph_id: &Uuid) -> Result<Option<StoredMapping>, String> {
+        Ok(self.mappings.read()
+            .map_err(|_| "Failed to acquire read lock for mappings".to_string())?
+            .get(graph_id)
+            .cloned())
+    }
+
+    fn store_vrp_instance(&self, instance: StoredVrpInstance) -> Result<Uuid, String> {
+        let instance_id = instance.id;
+        self.vrp_instances.write()
+            .map_err(|_| "Failed to acquire write lock for VRP instances".to_string())?
+            .insert(instance_id, instance);
+        Ok(instance_id)
+    }
+
+    fn get_vrp_instance(&self, vrp_id: &Uuid) -> Result<Option<StoredVrpInstance>, String> {
+        Ok(self.vrp_instances.read()
+            .map_err(|_| "Failed to acquire read lock for VRP instances".to_string())?
+            .get(vrp_id)
+            .cloned())
+    }
+
+    fn list_vrp_instances(&self) -> Result<Vec<Uuid>, String> {
+        Ok(self.vrp_instances.read()
+            .map_err(|_| "Failed to acquire read lock for VRP instances".to_string())?
+            .keys()
+            .cloned()
+            .collect())
+    }
+
+    fn store_solution(&self, solution: StoredSolution) -> Result<Uuid, String> {
+        let solution_id = solution.id;
+        self.solutions.write()
+            .map_err(|_| "Failed to acquire write lock for solutions".to_string())?
+            .insert(solution_id, solution);
+        Ok(solution_id)
+    }
+
+    fn get_solution(&self, solution_id: &Uuid) -> Result<Option<StoredSolution>, String> {
+        Ok(self.solutions.read()
+            .map_err(|_| "Failed to acquire read lock for solutions".to_string())?
+            .get(solution_id)
+            .cloned())
+    }
+
+    fn list_solutions(&self) -> Result<Vec<Uuid>, String> {
+        Ok(self.solutions.read()
+            .map_err(|_| "Failed to acquire read lock for solutions".to_string())?
+            .keys()
+            .cloned()
+            .collect())
+    }
+
+    fn get_solutions_for_vrp(&self, vrp_id: &Uuid) -> Result<Vec<StoredSolution>, String> {
+        Ok(self.solutions.read()
+            .map_err(|_| "Failed to acquire read lock for solutions".to_string())?
+            .values()
+            .filter(|s| &s.vrp_id == vrp_id)
+            .cloned()
+            .collect())
+    }
+
+    fn cleanup_old_data(&self, max_age_hours: u64) -> Result<(), String> {
+        let cutoff_time = SystemTime::now()
+            .checked_sub(Duration::from_secs(max_age_hours * 3600))
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        if let Ok(mut graphs) = self.graphs.write() {
+            graphs.retain(|_, graph| graph.created_at > cutoff_time);
+        }
+        if let Ok(mut mappings) = self.mappings.write() {
+            mappings.retain(|_, mapping| mapping.created_at > cutoff_time);
+        }
+        if let Ok(mut instances) = self.vrp_instances.write() {
+            instances.retain(|_, instance| instance.created_at > cutoff_time);
+        }
+        if let Ok(mut solutions) = self.solutions.write() {
+            solutions.retain(|_, solution| solution.created_at > cutoff_time);
+        }
+
+        Ok(())
+    }
+
+    fn get_stats(&self) -> Result<AppStateStats, String> {
+        Ok(AppStateStats {
+            graphs: self.graphs.read().map_err(|_| "Failed to acquire read lock for graphs".to_string())?.len(),
+            mappings: self.mappings.read().map_err(|_| "Failed to acquire read lock for mappings".to_string())?.len(),
+            vrp_instances: self.vrp_instances.read().map_err(|_| "Failed to acquire read lock for VRP instances".to_string())?.len(),
+            solutions: self.solutions.read().map_err(|_| "Failed to acquire read lock for solutions".to_string())?.len(),
+        })
+    }
+}
+
+/// A disk-backed backend built on `sled`. Each entity type gets its own
+/// tree, namespaced by the entity's UUID so keys never collide across
+/// trees; values are serialized with `serde_json` so they stay human
+/// readable on disk, matching how instances/solutions are already
+/// persisted to JSON files elsewhere in this crate.
+pub struct SledStore {
+    graphs: sled::Tree,
+    mappings: sled::Tree,
+    vrp_instances: sled::Tree,
+    solutions: sled::Tree,
+}
+
+impl SledStore {
+    pub fn open(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        let db = sled::open(path).map_err(|e| format!("Failed to open sled database: {}", e))?;
+        Ok(Self {
+            graphs: db.open_tree("graphs").map_err(|e| e.to_string())?,
+            mappings: db.open_tree("mappings").map_err(|e| e.to_string())?,
+            vrp_instances: db.open_tree("vrp_instances").map_err(|e| e.to_string())?,
+            solutions: db.open_tree("solutions").map_err(|e| e.to_string())?,
+        })
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(tree: &sled::Tree, key: &Uuid) -> Result<Option<T>, String> {
+        match tree.get(key.as_bytes()).map_err(|e| e.to_string())? {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| format!("Failed to deserialize stored value: {}", e)),
+            None => Ok(None),
+        }
+    }
+
+    fn put<T: serde::Serialize>(tree: &sled::Tree, key: &Uuid, value: &T) -> Result<(), String> {
+        let bytes = serde_json::to_vec(value).map_err(|e| format!("Failed to serialize value: {}", e))?;
+        tree.insert(key.as_bytes(), bytes).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    fn keys(tree: &sled::Tree) -> Result<Vec<Uuid>, String> {
+        tree.iter()
+            .keys()
+            .map(|key| {
+                let key = key.map_err(|e| e.to_string())?;
+                Uuid::from_slice(&key).map_err(|e| format!("Corrupt key in store: {}", e))
+            })
+            .collect()
+    }
+
+    fn values<T: serde::de::DeserializeOwned>(tree: &sled::Tree) -> Result<Vec<T>, String> {
+        tree.iter()
+            .values()
+            .map(|value| {
+                let value = value.map_err(|e| e.to_string())?;
+                serde_json::from_slice(&value).map_err(|e| format!("Failed to deserialize stored value: {}", e))
+            })
+            .collect()
+    }
+
+    fn retain_newer_than<T, F>(tree: &sled::Tree, cutoff: SystemTime, created_at: F) -> Result<(), String>
+    where
+        T: serde::de::DeserializeOwned,
+        F: Fn(&T) -> SystemTime,
+    {
+        for entry in tree.iter() {
+            let (key, value) = entry.map_err(|e| e.to_string())?;
+            let decoded: T = serde_json::from_slice(&value)
+                .map_err(|e| format!("Failed to deserialize stored value: {}", e))?;
+            if created_at(&decoded) <= cutoff {
+                tree.remove(key).map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Store for SledStore {
+    fn store_graph(&self, graph: StoredGraph) -> Result<Uuid, String> {
+        let graph_id = graph.id;
+        Self::put(&self.graphs, &graph_id, &graph)?;
+        Ok(graph_id)
+    }
+
+    fn get_graph(&self, graph_id: &Uuid) -> Result<Option<StoredGraph>, String> {
+        Self::get(&self.graphs, graph_id)
+    }
+
+    fn list_graphs(&self) -> Result<Vec<Uuid>, String> {
+        Self::keys(&self.graphs)
+    }
+
+    fn store_mapping(&self, mapping: StoredMapping) -> Result<(), String> {
+        Self::put(&self.mappings, &mapping.graph_id, &mapping)
+    }
+
+    fn get_mapping(&self, graph_id: &Uuid) -> Result<Option<StoredMapping>, String> {
+        Self::get(&self.mappings, graph_id)
+    }
+
+    fn store_vrp_instance(&self, instance: StoredVrpInstance) -> Result<Uuid, String> {
+        let instance_id = instance.id;
+        Self::put(&self.vrp_instances, &instance_id, &instance)?;
+        Ok(instance_id)
+    }
+
+    fn get_vrp_instance(&self, vrp_id: &Uuid) -> Result<Option<StoredVrpInstance>, String> {
+        Self::get(&self.vrp_instances, vrp_id)
+    }
+
+    fn list_vrp_instances(&self) -> Result<Vec<Uuid>, String> {
+        Self::keys(&self.vrp_instances)
+    }
+
+    fn store_solution(&self, solution: StoredSolution) -> Result<Uuid, String> {
+        let solution_id = solution.id;
+        Self::put(&self.solutions, &solution_id, &solution)?;
+        Ok(solution_id)
+    }
+
+    fn get_solution(&self, solution_id: &Uuid) -> Result<Option<StoredSolution>, String> {
+        Self::get(&self.solutions, solution_id)
+    }
+
+    fn list_solutions(&self) -> Result<Vec<Uuid>, String> {
+        Self::keys(&self.solutions)
+    }
+
+    fn get_solutions_for_vrp(&self, vrp_id: &Uuid) -> Result<Vec<StoredSolution>, String> {
+        Ok(Self::values::<StoredSolution>(&self.solutions)?
+            .into_iter()
+            .filter(|s| &s.vrp_id == vrp_id)
+            .collect())
+    }
+
+    fn cleanup_old_data(&self, max_age_hours: u64) -> Result<(), String> {
+        let cutoff_time = SystemTime::now()
+            .checked_sub(Duration::from_secs(max_age_hours * 3600))
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        Self::retain_newer_than::<StoredGraph, _>(&self.graphs, cutoff_time, |g| g.created_at)?;
+        Self::retain_newer_than::<StoredMapping, _>(&self.mappings, cutoff_time, |m| m.created_at)?;
+        Self::retain_newer_than::<StoredVrpInstance, _>(&self.vrp_instances, cutoff_time, |i| i.created_at)?;
+        Self::retain_newer_than::<StoredSolution, _>(&self.solutions, cutoff_time, |s| s.created_at)?;
+
+        Ok(())
+    }
+
+    fn get_stats(&self) -> Result<AppStateStats, String> {
+        Ok(AppStateStats {
+            graphs: self.graphs.len(),
+            mappings: self.mappings.len(),
+            vrp_instances: self.vrp_instances.len(),
+            solutions: self.solutions.len(),
+        })
+    }
+}
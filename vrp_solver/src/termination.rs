@@ -0,0 +1,159 @@
+//! Convergence-based termination control for iterative solvers
+//!
+//! Tracks max-iterations and max-time limits alongside a coefficient-of-
+//! variation (CV) convergence check over a sliding window of best objective
+//! values, so a solver can report *why* it stopped, not just that it did.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Stopping criteria for an iterative solver.
+#[derive(Debug, Clone, Copy)]
+pub struct TerminationConfig {
+    pub max_iterations: Option<usize>,
+    pub max_time_secs: Option<f64>,
+    /// Stop once the coefficient of variation (stddev / mean) of the best
+    /// objective over the last `window_size` iterations drops below this.
+    pub min_cv: Option<f64>,
+    pub window_size: usize,
+}
+
+impl Default for TerminationConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: Some(100),
+            max_time_secs: None,
+            min_cv: Some(0.001),
+            window_size: 5,
+        }
+    }
+}
+
+/// Which stopping criterion caused a solver to halt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminationReason {
+    MaxIterations,
+    MaxTime,
+    Converged,
+    Cancelled,
+}
+
+/// Tracks iteration count, elapsed time, and a sliding window of best
+/// objective values, reporting when a solver should stop.
+pub struct TerminationController {
+    config: TerminationConfig,
+    started_at: Instant,
+    iterations: usize,
+    window: VecDeque<f64>,
+}
+
+impl TerminationController {
+    pub fn new(config: TerminationConfig) -> Self {
+        Self {
+            config,
+            started_at: Instant::now(),
+            iterations: 0,
+            window: VecDeque::with_capacity(config.window_size.max(1)),
+        }
+    }
+
+    /// Record the best objective value found in the latest iteration and
+    /// check whether any configured stopping criterion now applies. Returns
+    /// `None` if the search should keep going.
+    pub fn record(&mut self, best_objective: f64) -> Option<TerminationReason> {
+        self.iterations += 1;
+
+        let window_size = self.config.window_size.max(1);
+        if self.window.len() == window_size {
+            self.window.pop_front();
+        }
+        self.window.push_back(best_objective);
+
+        // Never evaluate CV before the window is full - an early, noisy
+        // read would look falsely converged.
+        if let Some(min_cv) = self.config.min_cv {
+            if self.window.len() == window_size {
+                let mean = self.window.iter().sum::<f64>() / window_size as f64;
+                // Guard against mean ~= 0, where CV is undefined/unstable.
+                if mean.abs() > f64::EPSILON {
+                    let variance = self.window.iter()
+                        .map(|v| (v - mean).powi(2))
+                        .sum::<f64>() / window_size as f64;
+                    let cv = variance.sqrt() / mean.abs();
+                    if cv < min_cv {
+                        return Some(TerminationReason::Converged);
+                    }
+                }
+            }
+        }
+
+        if let Some(max_iterations) = self.config.max_iterations {
+            if self.iterations >= max_iterations {
+                return Some(TerminationReason::MaxIterations);
+            }
+        }
+
+        if let Some(max_time_secs) = self.config.max_time_secs {
+            if self.started_at.elapsed().as_secs_f64() >= max_time_secs {
+                return Some(TerminationReason::MaxTime);
+            }
+        }
+
+        None
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converges_on_flat_objective() {
+        let config = TerminationConfig {
+            max_iterations: Some(100),
+            max_time_secs: None,
+            min_cv: Some(0.001),
+            window_size: 3,
+        };
+        let mut controller = TerminationController::new(config);
+
+        assert!(controller.record(100.0).is_none());
+        assert!(controller.record(100.0).is_none());
+        assert_eq!(controller.record(100.0), Some(TerminationReason::Converged));
+    }
+
+    #[test]
+    fn test_stops_at_max_iterations_without_convergence() {
+        let config = TerminationConfig {
+            max_iterations: Some(3),
+            max_time_secs: None,
+            min_cv: None,
+            window_size: 2,
+        };
+        let mut controller = TerminationController::new(config);
+
+        assert!(controller.record(100.0).is_none());
+        assert!(controller.record(50.0).is_none());
+        assert_eq!(controller.record(75.0), Some(TerminationReason::MaxIterations));
+    }
+
+    #[test]
+    fn test_guards_against_zero_mean() {
+        let config = TerminationConfig {
+            max_iterations: Some(100),
+            max_time_secs: None,
+            min_cv: Some(0.001),
+            window_size: 2,
+        };
+        let mut controller = TerminationController::new(config);
+
+        assert!(controller.record(1.0).is_none());
+        assert_eq!(controller.record(-1.0), None);
+    }
+}
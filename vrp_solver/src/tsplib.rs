@@ -0,0 +1,446 @@
+//! Parser for TSPLIB/CVRPLIB `.tsp`/`.vrp` problem instances
+//!
+//! Supports the subset of the format needed to benchmark the solver against
+//! standard academic instances: `NODE_COORD_SECTION` with `EUC_2D`, `GEO`, and
+//! `ATT` edge weight types, `EXPLICIT` weights given in `EDGE_WEIGHT_SECTION`
+//! (`FULL_MATRIX`, `UPPER_ROW`, `LOWER_DIAG_ROW`), plus `DEMAND_SECTION`,
+//! `DEPOT_SECTION`, and `CAPACITY`.
+
+use crate::types::{Coordinate, Location, Vehicle, VrpInstance};
+use crate::{VrpError, VrpResult};
+use std::fs;
+use std::path::Path;
+
+/// Earth radius (km) used by the TSPLIB `GEO` distance convention.
+const TSPLIB_EARTH_RADIUS_KM: f64 = 6378.388;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EdgeWeightType {
+    Euc2D,
+    Geo,
+    Att,
+    Explicit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EdgeWeightFormat {
+    FullMatrix,
+    UpperRow,
+    LowerDiagRow,
+}
+
+/// Indexes into `lines`, erroring instead of panicking when a section's
+/// `DIMENSION`-declared entry count runs past the end of the file.
+fn next_line<'a>(lines: &[&'a str], i: usize) -> VrpResult<&'a str> {
+    lines.get(i).copied().ok_or_else(|| VrpError::InvalidInput("Unexpected end of file".to_string()))
+}
+
+/// Parse a TSPLIB/CVRPLIB `.tsp`/`.vrp` file into a [`VrpInstance`].
+pub fn parse_tsplib_file<P: AsRef<Path>>(path: P) -> VrpResult<VrpInstance> {
+    let content = fs::read_to_string(&path)
+        .map_err(|e| VrpError::InvalidInput(format!("Cannot open file {:?}: {}", path.as_ref(), e)))?;
+    parse_tsplib_str(&content)
+}
+
+/// Parse a TSPLIB/CVRPLIB document already loaded into memory.
+pub fn parse_tsplib_str(content: &str) -> VrpResult<VrpInstance> {
+    let mut dimension: Option<usize> = None;
+    let mut capacity: Option<f64> = None;
+    let mut edge_weight_type: Option<EdgeWeightType> = None;
+    let mut edge_weight_format: Option<EdgeWeightFormat> = None;
+
+    let mut coords: Vec<(usize, f64, f64)> = Vec::new();
+    let mut demands: Vec<(usize, f64)> = Vec::new();
+    let mut depot_ids: Vec<usize> = Vec::new();
+    let mut explicit_weights: Vec<f64> = Vec::new();
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].trim();
+        i += 1;
+
+        if line.is_empty() || line == "EOF" {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim();
+            let value = value.trim();
+            match key {
+                "DIMENSION" => {
+                    dimension = Some(value.parse().map_err(|_| {
+                        VrpError::InvalidInput(format!("Invalid DIMENSION: '{}'", value))
+                    })?);
+                }
+                "CAPACITY" => {
+                    capacity = Some(value.parse().map_err(|_| {
+                        VrpError::InvalidInput(format!("Invalid CAPACITY: '{}'", value))
+                    })?);
+                }
+                "EDGE_WEIGHT_TYPE" => {
+                    edge_weight_type = Some(match value {
+                        "EUC_2D" => EdgeWeightType::Euc2D,
+                        "GEO" => EdgeWeightType::Geo,
+                        "ATT" => EdgeWeightType::Att,
+                        "EXPLICIT" => EdgeWeightType::Explicit,
+                        other => {
+                            return Err(VrpError::InvalidInput(format!(
+                                "Unsupported EDGE_WEIGHT_TYPE: '{}'",
+                                other
+                            )))
+                        }
+                    });
+                }
+                "EDGE_WEIGHT_FORMAT" => {
+                    edge_weight_format = Some(match value {
+                        "FULL_MATRIX" => EdgeWeightFormat::FullMatrix,
+                        "UPPER_ROW" => EdgeWeightFormat::UpperRow,
+                        "LOWER_DIAG_ROW" => EdgeWeightFormat::LowerDiagRow,
+                        other => {
+                            return Err(VrpError::InvalidInput(format!(
+                                "Unsupported EDGE_WEIGHT_FORMAT: '{}'",
+                                other
+                            )))
+                        }
+                    });
+                }
+                _ => {} // NAME, TYPE, COMMENT, etc. are not needed
+            }
+            continue;
+        }
+
+        match line {
+            "NODE_COORD_SECTION" => {
+                let n = dimension.ok_or_else(|| {
+                    VrpError::InvalidInput("NODE_COORD_SECTION before DIMENSION".to_string())
+                })?;
+                for _ in 0..n {
+                    let fields: Vec<&str> = next_line(&lines, i)?.trim().split_whitespace().collect();
+                    i += 1;
+                    if fields.len() < 3 {
+                        return Err(VrpError::InvalidInput(
+                            "Malformed NODE_COORD_SECTION entry".to_string(),
+                        ));
+                    }
+                    let id: usize = fields[0].parse().map_err(|_| {
+                        VrpError::InvalidInput(format!("Invalid node id: '{}'", fields[0]))
+                    })?;
+                    let x: f64 = fields[1].parse().map_err(|_| {
+                        VrpError::InvalidInput(format!("Invalid coordinate: '{}'", fields[1]))
+                    })?;
+                    let y: f64 = fields[2].parse().map_err(|_| {
+                        VrpError::InvalidInput(format!("Invalid coordinate: '{}'", fields[2]))
+                    })?;
+                    coords.push((id, x, y));
+                }
+            }
+            "DEMAND_SECTION" => {
+                let n = dimension.ok_or_else(|| {
+                    VrpError::InvalidInput("DEMAND_SECTION before DIMENSION".to_string())
+                })?;
+                for _ in 0..n {
+                    let fields: Vec<&str> = next_line(&lines, i)?.trim().split_whitespace().collect();
+                    i += 1;
+                    if fields.len() < 2 {
+                        return Err(VrpError::InvalidInput(
+                            "Malformed DEMAND_SECTION entry".to_string(),
+                        ));
+                    }
+                    let id: usize = fields[0].parse().map_err(|_| {
+                        VrpError::InvalidInput(format!("Invalid node id: '{}'", fields[0]))
+                    })?;
+                    let demand: f64 = fields[1].parse().map_err(|_| {
+                        VrpError::InvalidInput(format!("Invalid demand: '{}'", fields[1]))
+                    })?;
+                    demands.push((id, demand));
+                }
+            }
+            "DEPOT_SECTION" => loop {
+                let value = next_line(&lines, i)?.trim();
+                i += 1;
+                let id: i64 = value.parse().map_err(|_| {
+                    VrpError::InvalidInput(format!("Invalid DEPOT_SECTION entry: '{}'", value))
+                })?;
+                if id == -1 {
+                    break;
+                }
+                depot_ids.push(id as usize);
+            },
+            "EDGE_WEIGHT_SECTION" => {
+                let n = dimension.ok_or_else(|| {
+                    VrpError::InvalidInput("EDGE_WEIGHT_SECTION before DIMENSION".to_string())
+                })?;
+                let format = edge_weight_format.unwrap_or(EdgeWeightFormat::FullMatrix);
+                let count = match format {
+                    EdgeWeightFormat::FullMatrix => n * n,
+                    EdgeWeightFormat::UpperRow => n * (n - 1) / 2,
+                    EdgeWeightFormat::LowerDiagRow => n * (n + 1) / 2,
+                };
+
+                let mut values = Vec::with_capacity(count);
+                while values.len() < count {
+                    let fields: Vec<&str> = next_line(&lines, i)?.trim().split_whitespace().collect();
+                    i += 1;
+                    for field in fields {
+                        let value: f64 = field.parse().map_err(|_| {
+                            VrpError::InvalidInput(format!("Invalid edge weight: '{}'", field))
+                        })?;
+                        values.push(value);
+                    }
+                }
+                explicit_weights = values;
+            }
+            _ => {} // Unsupported section, skip
+        }
+    }
+
+    let n = dimension
+        .ok_or_else(|| VrpError::InvalidInput("Missing DIMENSION".to_string()))?;
+    if coords.is_empty() && edge_weight_type != Some(EdgeWeightType::Explicit) {
+        return Err(VrpError::InvalidInput(
+            "Missing NODE_COORD_SECTION".to_string(),
+        ));
+    }
+
+    let demand_lookup: std::collections::HashMap<usize, f64> = demands.into_iter().collect();
+    let depot_id = depot_ids.first().copied().unwrap_or(1);
+
+    let mut locations = Vec::with_capacity(n);
+    if !coords.is_empty() {
+        for (id, x, y) in &coords {
+            let demand = demand_lookup.get(id).copied().unwrap_or(0.0);
+            let coordinate = Coordinate::new(*y, *x); // TSPLIB stores (x, y); treat as (lon-like, lat-like)
+            if *id == depot_id {
+                locations.push(Location::depot(*id, format!("Depot {}", id), coordinate));
+            } else {
+                locations.push(Location::new(
+                    *id,
+                    format!("Customer {}", id),
+                    coordinate,
+                    demand,
+                    None,
+                    0.0,
+                ));
+            }
+        }
+    } else {
+        // EXPLICIT-only instance: synthesize placeholder coordinates at the origin.
+        for id in 1..=n {
+            let demand = demand_lookup.get(&id).copied().unwrap_or(0.0);
+            let coordinate = Coordinate::new(0.0, 0.0);
+            if id == depot_id {
+                locations.push(Location::depot(id, format!("Depot {}", id), coordinate));
+            } else {
+                locations.push(Location::new(
+                    id,
+                    format!("Customer {}", id),
+                    coordinate,
+                    demand,
+                    None,
+                    0.0,
+                ));
+            }
+        }
+    }
+
+    let vehicle_capacity = capacity.unwrap_or(f64::INFINITY);
+    // TSPLIB CVRP instances don't state a fleet size; size it generously so the
+    // solver can spread demand across as many identical vehicles as it needs.
+    let num_vehicles = n.max(1);
+    let vehicles: Vec<Vehicle> = (0..num_vehicles)
+        .map(|i| Vehicle::new(i, vehicle_capacity, None, None, depot_id))
+        .collect();
+
+    let mut instance = VrpInstance::new(locations, vehicles);
+
+    match edge_weight_type.unwrap_or(EdgeWeightType::Euc2D) {
+        EdgeWeightType::Euc2D => {
+            fill_euc2d_matrix(&mut instance, &coords);
+        }
+        EdgeWeightType::Att => {
+            fill_att_matrix(&mut instance, &coords);
+        }
+        EdgeWeightType::Geo => {
+            fill_geo_matrix(&mut instance, &coords);
+        }
+        EdgeWeightType::Explicit => {
+            fill_explicit_matrix(
+                &mut instance,
+                &explicit_weights,
+                edge_weight_format.unwrap_or(EdgeWeightFormat::FullMatrix),
+                n,
+            )?;
+        }
+    }
+
+    Ok(instance)
+}
+
+/// Fill the distance matrix using plain Cartesian (not geographic) distance,
+/// per the TSPLIB `EUC_2D` convention: nodes are points on a plane, not
+/// lat/lon degrees, so this must not go through `distance::euclidean_distance`
+/// (which scales by meters-per-degree and is for [`Coordinate`]s instead).
+fn fill_euc2d_matrix(instance: &mut VrpInstance, coords: &[(usize, f64, f64)]) {
+    let n = instance.locations.len();
+    for i in 0..n {
+        for j in 0..n {
+            if i == j {
+                instance.distance_matrix[i][j] = 0.0;
+                continue;
+            }
+            let (_, xi, yi) = coords[i];
+            let (_, xj, yj) = coords[j];
+            let dx = xi - xj;
+            let dy = yi - yj;
+            instance.distance_matrix[i][j] = (dx * dx + dy * dy).sqrt().round();
+        }
+    }
+}
+
+/// Fill the distance matrix using the TSPLIB pseudo-Euclidean `ATT` formula.
+fn fill_att_matrix(instance: &mut VrpInstance, coords: &[(usize, f64, f64)]) {
+    let n = instance.locations.len();
+    for i in 0..n {
+        for j in 0..n {
+            if i == j {
+                instance.distance_matrix[i][j] = 0.0;
+                continue;
+            }
+            let (_, xi, yi) = coords[i];
+            let (_, xj, yj) = coords[j];
+            let dx = xi - xj;
+            let dy = yi - yj;
+            let rij = ((dx * dx + dy * dy) / 10.0).sqrt();
+            instance.distance_matrix[i][j] = rij.round();
+        }
+    }
+}
+
+/// Fill the distance matrix using the TSPLIB `GEO` convention (degrees encoded
+/// as DDD.MM, its own earth radius, and great-circle distance).
+fn fill_geo_matrix(instance: &mut VrpInstance, coords: &[(usize, f64, f64)]) {
+    let to_radians = |coord: f64| -> f64 {
+        let deg = coord.trunc();
+        let min = coord - deg;
+        std::f64::consts::PI * (deg + 5.0 * min / 3.0) / 180.0
+    };
+
+    let n = instance.locations.len();
+    let lat_rad: Vec<f64> = coords.iter().map(|&(_, x, _)| to_radians(x)).collect();
+    let lon_rad: Vec<f64> = coords.iter().map(|&(_, _, y)| to_radians(y)).collect();
+
+    for i in 0..n {
+        for j in 0..n {
+            if i == j {
+                instance.distance_matrix[i][j] = 0.0;
+                continue;
+            }
+            let q1 = (lon_rad[i] - lon_rad[j]).cos();
+            let q2 = (lat_rad[i] - lat_rad[j]).cos();
+            let q3 = (lat_rad[i] + lat_rad[j]).cos();
+            let distance = TSPLIB_EARTH_RADIUS_KM
+                * (0.5 * ((1.0 + q1) * q2 - (1.0 - q1) * q3)).acos()
+                + 1.0;
+            instance.distance_matrix[i][j] = distance.trunc();
+        }
+    }
+}
+
+/// Fill the distance matrix directly from an `EDGE_WEIGHT_SECTION`.
+fn fill_explicit_matrix(
+    instance: &mut VrpInstance,
+    weights: &[f64],
+    format: EdgeWeightFormat,
+    n: usize,
+) -> VrpResult<()> {
+    match format {
+        EdgeWeightFormat::FullMatrix => {
+            if weights.len() != n * n {
+                return Err(VrpError::InvalidInput(
+                    "EDGE_WEIGHT_SECTION size does not match FULL_MATRIX format".to_string(),
+                ));
+            }
+            for i in 0..n {
+                for j in 0..n {
+                    instance.distance_matrix[i][j] = weights[i * n + j];
+                }
+            }
+        }
+        EdgeWeightFormat::UpperRow => {
+            let mut idx = 0;
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    let w = weights[idx];
+                    idx += 1;
+                    instance.distance_matrix[i][j] = w;
+                    instance.distance_matrix[j][i] = w;
+                }
+            }
+        }
+        EdgeWeightFormat::LowerDiagRow => {
+            let mut idx = 0;
+            for i in 0..n {
+                for j in 0..=i {
+                    let w = weights[idx];
+                    idx += 1;
+                    instance.distance_matrix[i][j] = w;
+                    instance.distance_matrix[j][i] = w;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_euc_2d() {
+        let content = "\
+NAME: test
+TYPE: CVRP
+DIMENSION: 3
+EDGE_WEIGHT_TYPE: EUC_2D
+CAPACITY: 10
+NODE_COORD_SECTION
+1 0 0
+2 0 10
+3 10 0
+DEMAND_SECTION
+1 0
+2 3
+3 4
+DEPOT_SECTION
+1
+-1
+EOF
+";
+        let instance = parse_tsplib_str(content).unwrap();
+        assert_eq!(instance.num_locations(), 3);
+        assert_eq!(instance.get_location(2).unwrap().demand, 3.0);
+        assert!((instance.distance_matrix[0][1] - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_parse_explicit_full_matrix() {
+        let content = "\
+NAME: test
+TYPE: TSP
+DIMENSION: 3
+EDGE_WEIGHT_TYPE: EXPLICIT
+EDGE_WEIGHT_FORMAT: FULL_MATRIX
+EDGE_WEIGHT_SECTION
+0 5 9
+5 0 3
+9 3 0
+EOF
+";
+        let instance = parse_tsplib_str(content).unwrap();
+        assert_eq!(instance.distance_matrix[0][1], 5.0);
+        assert_eq!(instance.distance_matrix[1][2], 3.0);
+        assert_eq!(instance.distance_matrix[2][0], 9.0);
+    }
+}
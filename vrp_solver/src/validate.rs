@@ -1,17 +1,145 @@
 //! Route validation functions for VRP constraints
 
 use crate::distance::{calculate_route_distance, calculate_route_duration};
-use crate::types::{Route, Solution, VrpInstance};
+use crate::types::{Break, Route, Solution, Vehicle, VrpInstance};
 use crate::{VrpError, VrpResult};
+use serde::{Deserialize, Serialize};
+
+/// One visited stop's timing, as produced by `RouteValidator::compute_schedule`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    pub location_id: usize,
+    pub travel_time: f64,
+    pub arrival: f64,
+    pub waiting: f64,
+    pub service_start: f64,
+    pub service_end: f64,
+    /// Duration of a mandatory driver break taken after this stop (see
+    /// `Vehicle::break_rule`), folded into `departure`; `0.0` when none was
+    /// due yet.
+    pub break_duration: f64,
+    pub departure: f64,
+}
+
+/// A route's full per-stop timeline, walked depot-first by `compute_schedule`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteSchedule {
+    pub stops: Vec<ScheduleEntry>,
+}
+
+/// An activity's role in a `RouteActivities` timeline, mirroring
+/// vrp-pragmatic's `departure`/`service`/`arrival` solution activities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    DepartDepot,
+    Service,
+    ArriveDepot,
+}
+
+/// One activity in a `RouteActivities` timeline, as produced by
+/// `RouteValidator::compute_activities`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    pub location_id: usize,
+    pub kind: ActivityKind,
+    pub arrival: f64,
+    pub departure: f64,
+    /// Vehicle load carried away from this activity: `0.0` at `DepartDepot`,
+    /// cumulative demand served so far at each `Service` activity and at
+    /// `ArriveDepot`.
+    pub load: f64,
+    /// `vehicle.capacity - load` at this point in the route.
+    pub capacity_remaining: Option<f64>,
+}
+
+/// A route's full pragmatic-style activity timeline, as produced by
+/// `RouteValidator::compute_activities`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteActivities {
+    pub vehicle_id: usize,
+    pub activities: Vec<Activity>,
+}
+
+/// A single feasibility problem found while validating a route or solution,
+/// typed so callers can branch on `kind()` or pattern-match directly instead
+/// of parsing a human-readable message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    CapacityExceeded { demand: f64, capacity: f64 },
+    /// Like `CapacityExceeded`, but for one dimension of a multi-commodity
+    /// fleet (see `Location::demand_dims`/`Vehicle::capacity_dims`).
+    CapacityDimensionExceeded { dimension: usize, demand: f64, capacity: f64 },
+    TimeWindowMissed { location_id: usize, arrival: f64, window_end: f64 },
+    DistanceLimit { actual: f64, max: f64 },
+    DurationLimit { actual: f64, max: f64 },
+    /// A vehicle's `Vehicle::break_rule` wasn't scheduled within
+    /// `[earliest, latest]` before the route ran past `latest`.
+    BreakNotScheduled { earliest: f64, latest: f64 },
+    LocationNotFound { id: usize },
+    CustomerUnserved { id: usize },
+    CustomerServedTwice { id: usize, count: usize },
+}
+
+impl Violation {
+    /// A short, stable tag identifying this violation's variant, for
+    /// grouping/counting without a full `match` (see `ValidationResult::violations_of_kind`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Violation::CapacityExceeded { .. } => "capacity_exceeded",
+            Violation::CapacityDimensionExceeded { .. } => "capacity_dimension_exceeded",
+            Violation::TimeWindowMissed { .. } => "time_window_missed",
+            Violation::DistanceLimit { .. } => "distance_limit",
+            Violation::DurationLimit { .. } => "duration_limit",
+            Violation::BreakNotScheduled { .. } => "break_not_scheduled",
+            Violation::LocationNotFound { .. } => "location_not_found",
+            Violation::CustomerUnserved { .. } => "customer_unserved",
+            Violation::CustomerServedTwice { .. } => "customer_served_twice",
+        }
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::CapacityExceeded { demand, capacity } =>
+                write!(f, "Capacity violated: demand {} > capacity {}", demand, capacity),
+            Violation::CapacityDimensionExceeded { dimension, demand, capacity } =>
+                write!(f, "Capacity violated in dimension {}: demand {} > capacity {}", dimension, demand, capacity),
+            Violation::TimeWindowMissed { location_id, arrival, window_end } =>
+                write!(f, "Time window violated at location {}: arrival {} > window end {}", location_id, arrival, window_end),
+            Violation::DistanceLimit { actual, max } =>
+                write!(f, "Distance limit violated: {} > {}", actual, max),
+            Violation::DurationLimit { actual, max } =>
+                write!(f, "Duration limit violated: {} > {}", actual, max),
+            Violation::BreakNotScheduled { earliest, latest } =>
+                write!(f, "Mandatory break not scheduled within [{}, {}]", earliest, latest),
+            Violation::LocationNotFound { id } =>
+                write!(f, "Location {} in route not found in instance", id),
+            Violation::CustomerUnserved { id } =>
+                write!(f, "Customer {} was not served", id),
+            Violation::CustomerServedTwice { id, count } =>
+                write!(f, "Customer {} served {} time(s), expected exactly once", id, count),
+        }
+    }
+}
 
 /// Validation result for a route
 #[derive(Debug, Clone)]
 pub struct ValidationResult {
     pub is_valid: bool,
-    pub violations: Vec<String>,
+    pub violations: Vec<Violation>,
     pub capacity_utilization: f64,
+    /// Per-dimension utilization (`demand / capacity` for each dimension) for
+    /// multi-commodity vehicles; `None` when the vehicle has no `capacity_dims`.
+    /// `capacity_utilization` above is the max across these dimensions in
+    /// that case.
+    pub capacity_utilization_dims: Option<Vec<f64>>,
     pub distance_utilization: Option<f64>,
     pub duration_utilization: Option<f64>,
+    /// Weighted total overage cost from `RouteValidator::validate_route_soft`;
+    /// `0.0` for results from the hard `validate_route` path.
+    pub penalty: f64,
 }
 
 impl ValidationResult {
@@ -20,15 +148,22 @@ impl ValidationResult {
             is_valid: true,
             violations: Vec::new(),
             capacity_utilization: 0.0,
+            capacity_utilization_dims: None,
             distance_utilization: None,
             duration_utilization: None,
+            penalty: 0.0,
         }
     }
 
-    pub fn add_violation(&mut self, violation: String) {
+    pub fn add_violation(&mut self, violation: Violation) {
         self.is_valid = false;
         self.violations.push(violation);
     }
+
+    /// All violations whose `kind()` matches `kind`, e.g. `"capacity_exceeded"`.
+    pub fn violations_of_kind(&self, kind: &str) -> Vec<&Violation> {
+        self.violations.iter().filter(|v| v.kind() == kind).collect()
+    }
 }
 
 impl Default for ValidationResult {
@@ -37,6 +172,27 @@ impl Default for ValidationResult {
     }
 }
 
+/// Per-check weights used to turn a [`Violation`]'s overage into a penalty
+/// cost in [`RouteValidator::validate_route_soft`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConstraintPenalties {
+    pub capacity: f64,
+    pub time_window: f64,
+    pub distance: f64,
+    pub duration: f64,
+}
+
+impl Default for ConstraintPenalties {
+    fn default() -> Self {
+        Self {
+            capacity: 1.0,
+            time_window: 1.0,
+            distance: 1.0,
+            duration: 1.0,
+        }
+    }
+}
+
 /// Route validator with configurable constraints
 #[derive(Debug, Clone)]
 pub struct RouteValidator {
@@ -44,6 +200,11 @@ pub struct RouteValidator {
     pub check_time_windows: bool,
     pub check_distance_limits: bool,
     pub check_duration_limits: bool,
+    /// Whether to enforce a vehicle's `Vehicle::break_rule`, if it has one.
+    pub check_breaks: bool,
+    /// Weights used by `validate_route_soft` to score infeasible-but-close
+    /// routes instead of only rejecting them; unused by `validate_route`.
+    pub penalties: ConstraintPenalties,
 }
 
 impl Default for RouteValidator {
@@ -53,6 +214,8 @@ impl Default for RouteValidator {
             check_time_windows: true,
             check_distance_limits: true,
             check_duration_limits: true,
+            check_breaks: true,
+            penalties: ConstraintPenalties::default(),
         }
     }
 }
@@ -82,6 +245,16 @@ impl RouteValidator {
         self
     }
 
+    pub fn with_break_check(mut self, check: bool) -> Self {
+        self.check_breaks = check;
+        self
+    }
+
+    pub fn with_penalties(mut self, penalties: ConstraintPenalties) -> Self {
+        self.penalties = penalties;
+        self
+    }
+
     /// Validate a single route against all configured constraints
     pub fn validate_route(
         &self,
@@ -105,13 +278,15 @@ impl RouteValidator {
             .collect();
 
         if route_indices.len() != route.locations.len() {
-            result.add_violation("Some locations in route not found in instance".to_string());
+            for &id in route.locations.iter().filter(|&&id| instance.get_location(id).is_none()) {
+                result.add_violation(Violation::LocationNotFound { id });
+            }
             return Ok(result);
         }
 
         // Validate capacity constraints
         if self.check_capacity {
-            self.validate_capacity_constraint(instance, route, vehicle.capacity, &mut result);
+            self.validate_capacity_constraint(instance, route, vehicle, &mut result);
         }
 
         // Validate time window constraints
@@ -129,13 +304,25 @@ impl RouteValidator {
         // Validate duration limits
         if self.check_duration_limits {
             if let Some(max_duration) = vehicle.max_duration {
-                self.validate_duration_limit(instance, &route_indices, depot_idx, max_duration, &mut result)?;
+                self.validate_duration_limit(instance, &route_indices, depot_idx, max_duration, vehicle.break_rule.as_ref(), &mut result)?;
             }
         }
 
-        // Calculate utilization metrics
-        result.capacity_utilization = route.total_demand / vehicle.capacity;
-        
+        // Validate mandatory driver breaks
+        if self.check_breaks {
+            if let Some(break_rule) = &vehicle.break_rule {
+                self.validate_breaks(instance, &route_indices, depot_idx, break_rule, &mut result);
+            }
+        }
+
+        // Calculate utilization metrics. When capacity was already checked
+        // above, `validate_capacity_constraint` has set a more accurate
+        // value (summed from each location's real demand, and per-dimension
+        // when the vehicle is multi-commodity); don't clobber it here.
+        if !self.check_capacity {
+            result.capacity_utilization = route.total_demand / vehicle.capacity;
+        }
+
         if let Some(max_distance) = vehicle.max_distance {
             let actual_distance = calculate_route_distance(instance, &route_indices, depot_idx);
             result.distance_utilization = Some(actual_distance / max_distance);
@@ -150,30 +337,104 @@ impl RouteValidator {
         Ok(result)
     }
 
-    /// Validate capacity constraints for a route
+    /// Validates like `validate_route`, but also accumulates a weighted
+    /// `penalty` cost (`weight * overage` per violation) instead of only a
+    /// pass/fail `is_valid`, so a metaheuristic can rank infeasible-but-close
+    /// routes during search rather than rejecting every infeasible move.
+    pub fn validate_route_soft(&self, instance: &VrpInstance, route: &Route) -> VrpResult<ValidationResult> {
+        let mut result = self.validate_route(instance, route)?;
+        result.penalty = result.violations.iter().map(|v| self.penalty_of(v)).sum();
+        Ok(result)
+    }
+
+    fn penalty_of(&self, violation: &Violation) -> f64 {
+        match violation {
+            Violation::CapacityExceeded { demand, capacity } =>
+                self.penalties.capacity * (demand - capacity).max(0.0),
+            Violation::CapacityDimensionExceeded { demand, capacity, .. } =>
+                self.penalties.capacity * (demand - capacity).max(0.0),
+            Violation::TimeWindowMissed { arrival, window_end, .. } =>
+                self.penalties.time_window * (arrival - window_end).max(0.0),
+            Violation::DistanceLimit { actual, max } =>
+                self.penalties.distance * (actual - max).max(0.0),
+            Violation::DurationLimit { actual, max } =>
+                self.penalties.duration * (actual - max).max(0.0),
+            Violation::BreakNotScheduled { .. }
+            | Violation::LocationNotFound { .. }
+            | Violation::CustomerUnserved { .. }
+            | Violation::CustomerServedTwice { .. } => 0.0,
+        }
+    }
+
+    /// Validate capacity constraints for a route. For a multi-commodity
+    /// vehicle (`capacity_dims` set), each dimension is checked independently
+    /// against the matching entry of every visited location's `demand_dims`
+    /// (a location without `demand_dims` contributes its scalar `demand` to
+    /// dimension 0 only); `capacity_utilization` becomes the worst dimension's
+    /// utilization and the full per-dimension breakdown is recorded in
+    /// `capacity_utilization_dims`. Single-commodity vehicles keep the plain
+    /// scalar check.
     fn validate_capacity_constraint(
         &self,
         instance: &VrpInstance,
         route: &Route,
-        vehicle_capacity: f64,
+        vehicle: &Vehicle,
         result: &mut ValidationResult,
     ) {
+        if let Some(capacity_dims) = &vehicle.capacity_dims {
+            let dims = capacity_dims.len();
+            let mut totals = vec![0.0; dims];
+
+            for &location_id in &route.locations {
+                if let Some(location) = instance.get_location(location_id) {
+                    match &location.demand_dims {
+                        Some(demand_dims) => {
+                            for (total, demand) in totals.iter_mut().zip(demand_dims.iter()) {
+                                *total += demand;
+                            }
+                        }
+                        None if dims > 0 => totals[0] += location.demand,
+                        None => {}
+                    }
+                }
+            }
+
+            let mut utilization_dims = Vec::with_capacity(dims);
+            let mut worst_utilization = 0.0_f64;
+            for (dimension, (&total, &capacity)) in totals.iter().zip(capacity_dims.iter()).enumerate() {
+                if total > capacity {
+                    result.add_violation(Violation::CapacityDimensionExceeded {
+                        dimension,
+                        demand: total,
+                        capacity,
+                    });
+                }
+                let utilization = if capacity > 0.0 { total / capacity } else { 0.0 };
+                worst_utilization = worst_utilization.max(utilization);
+                utilization_dims.push(utilization);
+            }
+
+            result.capacity_utilization = worst_utilization;
+            result.capacity_utilization_dims = Some(utilization_dims);
+            return;
+        }
+
         let mut total_demand = 0.0;
-        
+
         for &location_id in &route.locations {
             if let Some(location) = instance.get_location(location_id) {
                 total_demand += location.demand;
             }
         }
 
-        if total_demand > vehicle_capacity {
-            result.add_violation(format!(
-                "Capacity violated: demand {} > capacity {}",
-                total_demand, vehicle_capacity
-            ));
+        if total_demand > vehicle.capacity {
+            result.add_violation(Violation::CapacityExceeded {
+                demand: total_demand,
+                capacity: vehicle.capacity,
+            });
         }
 
-        result.capacity_utilization = total_demand / vehicle_capacity;
+        result.capacity_utilization = total_demand / vehicle.capacity;
     }
 
     /// Validate time window constraints for a route
@@ -211,10 +472,11 @@ impl RouteValidator {
                     current_time = time_window.start;
                 } else if current_time > time_window.end {
                     // Arrive late, violation
-                    result.add_violation(format!(
-                        "Time window violated at location {}: arrival {} > window end {}",
-                        location.id, current_time, time_window.end
-                    ));
+                    result.add_violation(Violation::TimeWindowMissed {
+                        location_id: location.id,
+                        arrival: current_time,
+                        window_end: time_window.end,
+                    });
                 }
             }
             
@@ -226,6 +488,179 @@ impl RouteValidator {
         Ok(())
     }
 
+    /// Validates a vehicle's mandatory `break_rule`: walks the route's
+    /// accumulated time like `validate_time_windows` and, the first time it
+    /// falls inside `[earliest, latest]`, considers the break taken there
+    /// (adding `duration` to the running clock for the rest of the route).
+    /// If the route runs past `latest` without ever landing in that window,
+    /// the break couldn't be scheduled and a violation is recorded.
+    fn validate_breaks(
+        &self,
+        instance: &VrpInstance,
+        route_indices: &[usize],
+        depot_idx: usize,
+        break_rule: &Break,
+        result: &mut ValidationResult,
+    ) {
+        if route_indices.is_empty() {
+            return;
+        }
+
+        let time_matrix = match instance.time_matrix.as_ref() {
+            Some(matrix) => matrix,
+            None => return, // Cannot schedule a break without a time matrix
+        };
+
+        let mut current_time = 0.0;
+        let mut current_idx = depot_idx;
+        let mut break_taken = false;
+
+        for &location_idx in route_indices {
+            let location = &instance.locations[location_idx];
+
+            current_time += time_matrix[current_idx][location_idx];
+            if let Some(time_window) = location.time_window {
+                if current_time < time_window.start {
+                    current_time = time_window.start;
+                }
+            }
+            current_time += location.service_time;
+            current_idx = location_idx;
+
+            if !break_taken && current_time >= break_rule.earliest && current_time <= break_rule.latest {
+                current_time += break_rule.duration;
+                break_taken = true;
+            }
+        }
+
+        if !break_taken && current_time > break_rule.latest {
+            result.add_violation(Violation::BreakNotScheduled {
+                earliest: break_rule.earliest,
+                latest: break_rule.latest,
+            });
+        }
+    }
+
+    /// Walks a route depot-first like `validate_time_windows`, but records
+    /// the full per-stop timeline instead of only flagging violations: each
+    /// `ScheduleEntry`'s `waiting` covers arriving before a time window
+    /// opens, `service_start`/`service_end` bracket the service time, and
+    /// `departure` is when the vehicle moves on to the next stop. If the
+    /// vehicle has a `break_rule`, the first stop whose `service_end` falls
+    /// inside `[earliest, latest]` also gets `break_duration` added to its
+    /// departure, same as `validate_breaks`.
+    pub fn compute_schedule(&self, instance: &VrpInstance, route: &Route) -> VrpResult<RouteSchedule> {
+        let vehicle = instance.get_vehicle(route.vehicle_id)
+            .ok_or_else(|| VrpError::InvalidInput(format!("Vehicle {} not found", route.vehicle_id)))?;
+
+        let depot_idx = instance.locations
+            .iter()
+            .position(|loc| loc.id == vehicle.depot_id)
+            .ok_or_else(|| VrpError::InvalidInput(format!("Depot {} not found", vehicle.depot_id)))?;
+
+        let route_indices: Vec<usize> = route.locations
+            .iter()
+            .filter_map(|&id| instance.locations.iter().position(|loc| loc.id == id))
+            .collect();
+
+        let time_matrix = instance.time_matrix.as_ref()
+            .ok_or_else(|| VrpError::InvalidInput("Cannot compute a schedule without a time matrix".to_string()))?;
+
+        let mut stops = Vec::with_capacity(route_indices.len());
+        let mut current_time = 0.0;
+        let mut current_idx = depot_idx;
+        let mut break_taken = false;
+
+        for &location_idx in &route_indices {
+            let location = &instance.locations[location_idx];
+
+            let travel_time = time_matrix[current_idx][location_idx];
+            let arrival = current_time + travel_time;
+            let waiting = location.time_window
+                .map(|window| (window.start - arrival).max(0.0))
+                .unwrap_or(0.0);
+            let service_start = arrival + waiting;
+            let service_end = service_start + location.service_time;
+
+            let break_duration = match &vehicle.break_rule {
+                Some(break_rule) if !break_taken
+                    && service_end >= break_rule.earliest
+                    && service_end <= break_rule.latest =>
+                {
+                    break_taken = true;
+                    break_rule.duration
+                }
+                _ => 0.0,
+            };
+            let departure = service_end + break_duration;
+
+            stops.push(ScheduleEntry {
+                location_id: location.id,
+                travel_time,
+                arrival,
+                waiting,
+                service_start,
+                service_end,
+                break_duration,
+                departure,
+            });
+
+            current_time = departure;
+            current_idx = location_idx;
+        }
+
+        Ok(RouteSchedule { stops })
+    }
+
+    /// Like [`Self::compute_schedule`], but reports a full vrp-pragmatic-style
+    /// activity timeline: a `DepartDepot` activity, one `Service` activity
+    /// per stop (carrying the running vehicle load after that stop, and
+    /// remaining capacity), and a closing `ArriveDepot` activity.
+    pub fn compute_activities(&self, instance: &VrpInstance, route: &Route) -> VrpResult<RouteActivities> {
+        let vehicle = instance.get_vehicle(route.vehicle_id)
+            .ok_or_else(|| VrpError::InvalidInput(format!("Vehicle {} not found", route.vehicle_id)))?;
+
+        let schedule = self.compute_schedule(instance, route)?;
+
+        let mut activities = Vec::with_capacity(schedule.stops.len() + 2);
+        activities.push(Activity {
+            location_id: vehicle.depot_id,
+            kind: ActivityKind::DepartDepot,
+            arrival: 0.0,
+            departure: 0.0,
+            load: 0.0,
+            capacity_remaining: Some(vehicle.capacity),
+        });
+
+        let mut load = 0.0;
+        for stop in &schedule.stops {
+            let location = instance.get_location(stop.location_id)
+                .ok_or_else(|| VrpError::InvalidInput(format!("Location {} not found", stop.location_id)))?;
+            load += location.demand;
+
+            activities.push(Activity {
+                location_id: stop.location_id,
+                kind: ActivityKind::Service,
+                arrival: stop.arrival,
+                departure: stop.departure,
+                load,
+                capacity_remaining: Some(vehicle.capacity - load),
+            });
+        }
+
+        let last_departure = schedule.stops.last().map(|stop| stop.departure).unwrap_or(0.0);
+        activities.push(Activity {
+            location_id: vehicle.depot_id,
+            kind: ActivityKind::ArriveDepot,
+            arrival: last_departure,
+            departure: last_departure,
+            load,
+            capacity_remaining: Some(vehicle.capacity - load),
+        });
+
+        Ok(RouteActivities { vehicle_id: route.vehicle_id, activities })
+    }
+
     /// Validate distance limit constraints for a route
     fn validate_distance_limit(
         &self,
@@ -238,33 +673,83 @@ impl RouteValidator {
         let actual_distance = calculate_route_distance(instance, route_indices, depot_idx);
         
         if actual_distance > max_distance {
-            result.add_violation(format!(
-                "Distance limit violated: {} > {}",
-                actual_distance, max_distance
-            ));
+            result.add_violation(Violation::DistanceLimit {
+                actual: actual_distance,
+                max: max_distance,
+            });
         }
     }
 
-    /// Validate duration limit constraints for a route
+    /// Validate duration limit constraints for a route. When `break_rule` is
+    /// set, the mandatory break's `duration` is folded into `actual_duration`
+    /// before comparing against `max_duration`, but only if the break would
+    /// actually be taken per [`Self::validate_breaks`]'s per-stop walk — not
+    /// whenever the unbroken duration merely reaches `earliest`, which can be
+    /// true even when the route jumps straight past `[earliest, latest]`
+    /// between two stops and no break is ever scheduled.
     fn validate_duration_limit(
         &self,
         instance: &VrpInstance,
         route_indices: &[usize],
         depot_idx: usize,
         max_duration: f64,
+        break_rule: Option<&Break>,
         result: &mut ValidationResult,
     ) -> VrpResult<()> {
-        if let Some(actual_duration) = calculate_route_duration(instance, route_indices, depot_idx) {
+        if let Some(mut actual_duration) = calculate_route_duration(instance, route_indices, depot_idx) {
+            if let Some(break_rule) = break_rule {
+                actual_duration += self.break_duration_taken(instance, route_indices, depot_idx, break_rule);
+            }
             if actual_duration > max_duration {
-                result.add_violation(format!(
-                    "Duration limit violated: {} > {}",
-                    actual_duration, max_duration
-                ));
+                result.add_violation(Violation::DurationLimit {
+                    actual: actual_duration,
+                    max: max_duration,
+                });
             }
         }
         Ok(())
     }
 
+    /// Walks the route exactly like [`Self::validate_breaks`], returning how
+    /// much of `break_rule.duration` actually gets folded into the schedule:
+    /// `duration` if some stop's cumulative time lands inside
+    /// `[earliest, latest]`, `0.0` if the break is never taken (e.g. the
+    /// window is jumped over between two stops).
+    fn break_duration_taken(
+        &self,
+        instance: &VrpInstance,
+        route_indices: &[usize],
+        depot_idx: usize,
+        break_rule: &Break,
+    ) -> f64 {
+        let time_matrix = match instance.time_matrix.as_ref() {
+            Some(matrix) => matrix,
+            None => return 0.0,
+        };
+
+        let mut current_time = 0.0;
+        let mut current_idx = depot_idx;
+
+        for &location_idx in route_indices {
+            let location = &instance.locations[location_idx];
+
+            current_time += time_matrix[current_idx][location_idx];
+            if let Some(time_window) = location.time_window {
+                if current_time < time_window.start {
+                    current_time = time_window.start;
+                }
+            }
+            current_time += location.service_time;
+            current_idx = location_idx;
+
+            if current_time >= break_rule.earliest && current_time <= break_rule.latest {
+                return break_rule.duration;
+            }
+        }
+
+        0.0
+    }
+
     /// Validate an entire solution
     pub fn validate_solution(
         &self,
@@ -310,20 +795,13 @@ impl RouteValidator {
         }
 
         // Check for unserved customers
-        let unserved: Vec<usize> = all_customers.difference(&served_customers).copied().collect();
-        if !unserved.is_empty() {
-            result.add_violation(format!("Unserved customers: {:?}", unserved));
+        for &id in all_customers.difference(&served_customers) {
+            result.add_violation(Violation::CustomerUnserved { id });
         }
 
         // Check for customers served multiple times
-        let multiple_service: Vec<(usize, usize)> = customer_count
-            .iter()
-            .filter(|(_, &count)| count > 1)
-            .map(|(&id, &count)| (id, count))
-            .collect();
-        
-        if !multiple_service.is_empty() {
-            result.add_violation(format!("Customers served multiple times: {:?}", multiple_service));
+        for (&id, &count) in customer_count.iter().filter(|(_, &count)| count > 1) {
+            result.add_violation(Violation::CustomerServedTwice { id, count });
         }
 
         Ok(result)
@@ -368,7 +846,12 @@ pub fn get_validation_report(
         report.push_str(&format!("  Demand: {:.2}\n", route.total_demand));
         report.push_str(&format!("  Valid: {}\n", validation.is_valid));
         report.push_str(&format!("  Capacity Utilization: {:.2}%\n", validation.capacity_utilization * 100.0));
-        
+
+        if let Some(dims_util) = &validation.capacity_utilization_dims {
+            let formatted: Vec<String> = dims_util.iter().map(|u| format!("{:.2}%", u * 100.0)).collect();
+            report.push_str(&format!("  Capacity Utilization (per dimension): [{}]\n", formatted.join(", ")));
+        }
+
         if let Some(dist_util) = validation.distance_utilization {
             report.push_str(&format!("  Distance Utilization: {:.2}%\n", dist_util * 100.0));
         }
@@ -405,7 +888,7 @@ pub fn get_validation_report(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::distance::{calculate_distance_matrix, DistanceMethod};
+    use crate::distance::{calculate_distance_matrix, calculate_time_matrix, DistanceMethod};
     use crate::types::*;
 
     fn create_test_instance() -> VrpInstance {
@@ -459,6 +942,160 @@ mod tests {
         assert!(!result.violations.is_empty());
     }
 
+    #[test]
+    fn test_soft_validation_penalty() {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(1.0, 0.0), 25.0, None, 0.0),
+        ];
+        let vehicles = vec![Vehicle::new(0, 15.0, None, None, 0)];
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+
+        let validator = RouteValidator::new()
+            .with_time_window_check(false)
+            .with_distance_limit_check(false)
+            .with_duration_limit_check(false)
+            .with_penalties(ConstraintPenalties { capacity: 2.0, ..ConstraintPenalties::default() });
+
+        let mut route = Route::new(0);
+        route.add_location(1);
+
+        let result = validator.validate_route_soft(&instance, &route).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.penalty, 20.0); // weight 2.0 * overage (25 - 15 = 10)
+    }
+
+    #[test]
+    fn test_multi_dimensional_capacity_violation() {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(1.0, 0.0), 0.0, None, 0.0)
+                .with_demand_dims(vec![5.0, 20.0]),
+        ];
+        let vehicles = vec![Vehicle::new(0, 0.0, None, None, 0).with_capacity_dims(vec![10.0, 10.0])];
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+
+        let validator = RouteValidator::new()
+            .with_time_window_check(false)
+            .with_distance_limit_check(false)
+            .with_duration_limit_check(false);
+
+        let mut route = Route::new(0);
+        route.add_location(1);
+
+        let result = validator.validate_route(&instance, &route).unwrap();
+        assert!(!result.is_valid);
+        // Dimension 0 (weight 5 <= 10) is fine; dimension 1 (volume 20 > 10) isn't.
+        assert_eq!(
+            result.violations_of_kind("capacity_dimension_exceeded").len(),
+            1
+        );
+        assert_eq!(result.capacity_utilization_dims, Some(vec![0.5, 2.0]));
+        assert_eq!(result.capacity_utilization, 2.0);
+    }
+
+    #[test]
+    fn test_break_not_scheduled() {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(1.0, 0.0), 5.0, None, 0.0),
+        ];
+        // Travel time to the only stop is 1.0 (Euclidean distance of one
+        // degree, 111,320 m, at 111,320 m/s), which
+        // jumps straight past the break's [earliest, latest] = [0.2, 0.5]
+        // window since breaks are only checked at stop boundaries.
+        let vehicles = vec![
+            Vehicle::new(0, 10.0, None, None, 0)
+                .with_break(Break { earliest: 0.2, latest: 0.5, duration: 1.0 }),
+        ];
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+        calculate_time_matrix(&mut instance, 111_320.0);
+
+        let validator = RouteValidator::new()
+            .with_time_window_check(false)
+            .with_distance_limit_check(false)
+            .with_duration_limit_check(false);
+
+        let mut route = Route::new(0);
+        route.add_location(1);
+
+        let result = validator.validate_route(&instance, &route).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.violations_of_kind("break_not_scheduled").len(), 1);
+    }
+
+    #[test]
+    fn test_break_scheduled_folds_into_schedule() {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(1.0, 0.0), 5.0, None, 0.0),
+            Location::new(2, "Customer 2".to_string(), Coordinate::new(2.0, 0.0), 5.0, None, 0.0),
+        ];
+        // Reaching customer 1 lands at time 1.0, inside [0.0, 2.0]: the break
+        // is taken there, adding 3.0 before the vehicle moves on.
+        let vehicles = vec![
+            Vehicle::new(0, 10.0, None, None, 0)
+                .with_break(Break { earliest: 0.0, latest: 2.0, duration: 3.0 }),
+        ];
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+        calculate_time_matrix(&mut instance, 111_320.0); // one degree (111,320 m) per time unit
+
+        let validator = RouteValidator::new()
+            .with_time_window_check(false)
+            .with_distance_limit_check(false)
+            .with_duration_limit_check(false);
+
+        let mut route = Route::new(0);
+        route.add_location(1);
+        route.add_location(2);
+
+        let result = validator.validate_route(&instance, &route).unwrap();
+        assert!(result.violations_of_kind("break_not_scheduled").is_empty());
+
+        let schedule = validator.compute_schedule(&instance, &route).unwrap();
+        assert_eq!(schedule.stops[0].break_duration, 3.0);
+        assert_eq!(schedule.stops[0].departure, 4.0); // service_end 1.0 + break 3.0
+        assert_eq!(schedule.stops[1].arrival, 5.0); // departs at 4.0, travels 1.0
+    }
+
+    #[test]
+    fn test_duration_limit_not_inflated_by_unscheduled_break() {
+        // Same jumped-over-break scenario as `test_break_not_scheduled`, but
+        // with both duration-limit and break checks enabled together: the
+        // break is never taken, so it must not be folded into the duration
+        // used for the `max_duration` comparison even though the raw
+        // unbroken duration already reaches `earliest`.
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(1.0, 0.0), 5.0, None, 0.0),
+        ];
+        let vehicles = vec![
+            Vehicle::new(0, 10.0, None, Some(2.5), 0)
+                .with_break(Break { earliest: 0.2, latest: 0.5, duration: 1.0 }),
+        ];
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Euclidean);
+        calculate_time_matrix(&mut instance, 111_320.0); // one degree (111,320 m) per time unit
+
+        let validator = RouteValidator::new()
+            .with_time_window_check(false)
+            .with_distance_limit_check(false);
+
+        let mut route = Route::new(0);
+        route.add_location(1);
+
+        let result = validator.validate_route(&instance, &route).unwrap();
+        // Unbroken duration is depot->cust (1.0) + cust->depot (1.0) = 2.0,
+        // under the 2.5 limit. Folding in the unscheduled break's duration
+        // would wrongly push it to 3.0 and raise a phantom DurationLimit.
+        assert!(result.violations_of_kind("duration_limit").is_empty());
+        assert_eq!(result.violations_of_kind("break_not_scheduled").len(), 1);
+    }
+
     #[test]
     fn test_solution_validation() {
         let instance = create_test_instance();
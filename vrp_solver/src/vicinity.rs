@@ -0,0 +1,284 @@
+//! Vicinity clustering: a pre-processing step that merges stops close enough
+//! together to be served as one parked visit, so [`VrpSolver`](crate::solver::VrpSolver)
+//! implementations route the cluster as a single leg instead of one leg per
+//! member. Run [`VicinityClustering::cluster`] before solving, then
+//! [`VicinityClustering::expand`] the resulting [`Solution`] back into its
+//! original stops for display.
+
+use crate::distance::{calculate_route_distance, calculate_route_duration};
+use crate::types::{Location, Route, Solution, VrpInstance};
+use std::collections::HashMap;
+
+/// Groups customers within `distance_threshold`/`duration_threshold` of a
+/// cluster seed into a single synthetic stop, up to `max_jobs_per_cluster`
+/// members, charging `parking_time` once per cluster visit instead of once
+/// per member.
+#[derive(Debug, Clone, Copy)]
+pub struct VicinityClustering {
+    pub distance_threshold: f64,
+    pub duration_threshold: f64,
+    pub max_jobs_per_cluster: usize,
+    pub parking_time: f64,
+}
+
+impl VicinityClustering {
+    pub fn new(
+        distance_threshold: f64,
+        duration_threshold: f64,
+        max_jobs_per_cluster: usize,
+        parking_time: f64,
+    ) -> Self {
+        Self {
+            distance_threshold,
+            duration_threshold,
+            max_jobs_per_cluster,
+            parking_time,
+        }
+    }
+
+    fn within_threshold(&self, instance: &VrpInstance, a: usize, b: usize) -> bool {
+        if instance.distance_matrix[a][b] > self.distance_threshold {
+            return false;
+        }
+        if let Some(time_matrix) = &instance.time_matrix {
+            if time_matrix[a][b] > self.duration_threshold {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Greedily group customers (locations with positive demand) into
+    /// clusters seeded one at a time, in location order; depots and other
+    /// zero-demand locations are left untouched.
+    fn build_clusters(&self, instance: &VrpInstance) -> Vec<Vec<usize>> {
+        let customer_indices: Vec<usize> = instance.locations.iter().enumerate()
+            .filter(|(_, loc)| loc.demand > 0.0)
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let mut clustered = vec![false; instance.locations.len()];
+        let mut clusters = Vec::new();
+
+        for &seed in &customer_indices {
+            if clustered[seed] {
+                continue;
+            }
+            clustered[seed] = true;
+            let mut members = vec![seed];
+
+            for &candidate in &customer_indices {
+                if members.len() >= self.max_jobs_per_cluster {
+                    break;
+                }
+                if clustered[candidate] {
+                    continue;
+                }
+                if self.within_threshold(instance, seed, candidate) {
+                    clustered[candidate] = true;
+                    members.push(candidate);
+                }
+            }
+
+            clusters.push(members);
+        }
+
+        clusters
+    }
+
+    /// Build a reduced instance where every multi-member cluster is replaced
+    /// by one synthetic location, keyed off its seed's coordinate so the new
+    /// distance/time matrices can be sliced straight out of `instance`'s.
+    pub fn cluster(&self, instance: &VrpInstance) -> ClusteredInstance {
+        let clusters = self.build_clusters(instance);
+
+        let non_customers: Vec<usize> = instance.locations.iter().enumerate()
+            .filter(|(_, loc)| loc.demand <= 0.0)
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let next_id = instance.locations.iter().map(|loc| loc.id).max().unwrap_or(0) + 1;
+
+        let mut new_locations = Vec::with_capacity(non_customers.len() + clusters.len());
+        let mut representative = Vec::with_capacity(new_locations.capacity());
+        let mut members = HashMap::new();
+
+        for idx in non_customers {
+            representative.push(idx);
+            new_locations.push(instance.locations[idx].clone());
+        }
+
+        for (cluster_idx, group) in clusters.into_iter().enumerate() {
+            let seed = group[0];
+            if group.len() == 1 {
+                representative.push(seed);
+                new_locations.push(instance.locations[seed].clone());
+                continue;
+            }
+
+            let seed_location = &instance.locations[seed];
+            let cluster_id = next_id + cluster_idx;
+            let demand: f64 = group.iter().map(|&idx| instance.locations[idx].demand).sum();
+            let service_time: f64 = group.iter().map(|&idx| instance.locations[idx].service_time).sum::<f64>()
+                + self.parking_time;
+
+            let cluster_location = Location::new(
+                cluster_id,
+                format!("Cluster ({} stops near {})", group.len(), seed_location.name),
+                seed_location.coordinate,
+                demand,
+                None,
+                service_time,
+            );
+
+            representative.push(seed);
+            members.insert(cluster_id, group.iter().map(|&idx| instance.locations[idx].id).collect());
+            new_locations.push(cluster_location);
+        }
+
+        let n = representative.len();
+        let mut distance_matrix = vec![vec![0.0; n]; n];
+        let mut time_matrix = instance.time_matrix.as_ref().map(|_| vec![vec![0.0; n]; n]);
+
+        for i in 0..n {
+            for j in 0..n {
+                distance_matrix[i][j] = instance.distance_matrix[representative[i]][representative[j]];
+                if let (Some(new_times), Some(old_times)) = (time_matrix.as_mut(), &instance.time_matrix) {
+                    new_times[i][j] = old_times[representative[i]][representative[j]];
+                }
+            }
+        }
+
+        let mut clustered_instance = VrpInstance::new(new_locations, instance.vehicles.clone());
+        clustered_instance.distance_matrix = distance_matrix;
+        clustered_instance.time_matrix = time_matrix;
+
+        ClusteredInstance {
+            instance: clustered_instance,
+            mapping: ClusterMapping { members },
+        }
+    }
+
+    /// Expand every cluster stop in `solution` back into its member
+    /// locations, recomputing each route's demand/distance/duration against
+    /// `original_instance`.
+    pub fn expand(&self, solution: &Solution, mapping: &ClusterMapping, original_instance: &VrpInstance) -> Solution {
+        let mut expanded = Solution::new();
+
+        for route in &solution.routes {
+            let mut new_route = Route::new(route.vehicle_id);
+            let mut parking_visits = 0usize;
+
+            for &loc_id in &route.locations {
+                match mapping.members.get(&loc_id) {
+                    Some(member_ids) => {
+                        parking_visits += 1;
+                        for &member_id in member_ids {
+                            new_route.add_location(member_id);
+                        }
+                    }
+                    None => new_route.add_location(loc_id),
+                }
+            }
+
+            new_route.total_demand = new_route.locations.iter()
+                .filter_map(|&id| original_instance.get_location(id))
+                .map(|loc| loc.demand)
+                .sum();
+
+            if let Some(vehicle) = original_instance.get_vehicle(route.vehicle_id) {
+                if let Some(depot_idx) = original_instance.locations.iter().position(|loc| loc.id == vehicle.depot_id) {
+                    let route_indices: Vec<usize> = new_route.locations.iter()
+                        .filter_map(|&id| original_instance.locations.iter().position(|loc| loc.id == id))
+                        .collect();
+
+                    new_route.total_distance = calculate_route_distance(original_instance, &route_indices, depot_idx);
+
+                    if let Some(duration) = calculate_route_duration(original_instance, &route_indices, depot_idx) {
+                        new_route.total_duration = duration + parking_visits as f64 * self.parking_time;
+                    }
+                }
+            }
+
+            expanded.add_route(new_route);
+        }
+
+        expanded
+    }
+}
+
+/// A [`VrpInstance`] with dense clusters collapsed to single stops, plus the
+/// [`ClusterMapping`] needed to expand a solved [`Solution`] back out.
+#[derive(Debug, Clone)]
+pub struct ClusteredInstance {
+    pub instance: VrpInstance,
+    pub mapping: ClusterMapping,
+}
+
+/// Maps a synthetic cluster location id to the original location ids it
+/// stands in for. Only clusters with more than one member are present.
+#[derive(Debug, Clone, Default)]
+pub struct ClusterMapping {
+    members: HashMap<usize, Vec<usize>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::{calculate_distance_matrix, calculate_time_matrix, DistanceMethod};
+    use crate::solver::{GreedyNearestNeighbor, VrpSolver};
+    use crate::types::*;
+
+    fn create_test_instance() -> VrpInstance {
+        let locations = vec![
+            Location::depot(0, "Depot".to_string(), Coordinate::new(0.0, 0.0)),
+            // Customers 1 and 2 sit a few meters apart (same building).
+            Location::new(1, "Customer 1".to_string(), Coordinate::new(0.0001, 0.0), 5.0, None, 2.0),
+            Location::new(2, "Customer 2".to_string(), Coordinate::new(0.0002, 0.0), 5.0, None, 2.0),
+            // Customer 3 is far away and should stay its own stop.
+            Location::new(3, "Customer 3".to_string(), Coordinate::new(1.0, 1.0), 5.0, None, 2.0),
+        ];
+        let vehicles = vec![Vehicle::new(0, 100.0, None, None, 0)];
+
+        let mut instance = VrpInstance::new(locations, vehicles);
+        calculate_distance_matrix(&mut instance, DistanceMethod::Haversine);
+        calculate_time_matrix(&mut instance, 10.0);
+        instance
+    }
+
+    #[test]
+    fn test_cluster_merges_nearby_customers() {
+        let instance = create_test_instance();
+        let clustering = VicinityClustering::new(50.0, 60.0, 4, 30.0);
+
+        let clustered = clustering.cluster(&instance);
+
+        // Depot + merged cluster(1,2) + customer 3 = 3 locations.
+        assert_eq!(clustered.instance.locations.len(), 3);
+        assert_eq!(clustered.mapping.members.len(), 1);
+
+        let (&cluster_id, members) = clustered.mapping.members.iter().next().unwrap();
+        let mut sorted_members = members.clone();
+        sorted_members.sort();
+        assert_eq!(sorted_members, vec![1, 2]);
+
+        let cluster_location = clustered.instance.get_location(cluster_id).unwrap();
+        assert_eq!(cluster_location.demand, 10.0);
+    }
+
+    #[test]
+    fn test_expand_restores_original_stops_and_adds_parking_time() {
+        let instance = create_test_instance();
+        let clustering = VicinityClustering::new(50.0, 60.0, 4, 30.0);
+        let clustered = clustering.cluster(&instance);
+
+        let solution = GreedyNearestNeighbor::new().solve(&clustered.instance).unwrap();
+        let expanded = clustering.expand(&solution, &clustered.mapping, &instance);
+
+        let all_ids: Vec<usize> = expanded.routes.iter().flat_map(|r| r.locations.clone()).collect();
+        let mut sorted_ids = all_ids.clone();
+        sorted_ids.sort();
+        assert_eq!(sorted_ids, vec![1, 2, 3]);
+        assert!(expanded.total_duration >= solution.total_duration);
+    }
+}